@@ -1,87 +1,827 @@
-use crate::engine::{Game, GameOptions, GameStatus, GuessResult};
+use crate::engine::{
+    consistent_words, suggest, Game, GameError, GameOptions, GameResult, GameStatus, GuessResult,
+    HitAccuracy, Session, Stats,
+};
 use crate::theme::Theme;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::tutorial::{TutorialStep, TUTORIAL_ANSWER};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tui::layout::Rect;
 
-#[derive(PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum Disclaimer {
     MoveFeedback(GuessResult),
+    // a guess that was played in guided mode, and the hard mode rule it broke
+    HardModeWarning(GuessResult),
     GameWonMessage,
     GameOverMessage(String),
+    IdleHintMessage(String),
+    // the answers, or None in absurd mode before there is one
+    CheatAnswerMessage(Option<String>),
+    // the position, counting from 1, and the letter that goes there
+    LetterHintMessage(usize, char),
+    FriendRowMessage(usize, bool),
+    // an earlier guess picked with the arrow keys, counting from 0, to be read out letter by letter
+    ReviewedRowMessage(usize),
+    NoValidGuessesMessage,
+    TutorialMessage(TutorialStep),
     WelcomeMessage,
+    NewGameMessage,
+    ResumedGameMessage,
+    // today's puzzle was already finished on an earlier run, so there's nothing to play
+    DailyDoneMessage,
+}
+
+// How much help is on offer. Each level has everything the one before it has
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub enum AssistLevel {
+    // no hints and no suggested words
+    Off,
+    // Tab reveals a letter, and idling on a row offers a hint about the letters found so far
+    Hints,
+    // the solver suggests words too, as the opener in the welcome message and when still stuck
+    Candidates,
+}
+
+// how long the row stays red after a guess is turned down
+const ERROR_FLASH: Duration = Duration::from_millis(400);
+
+// a key on the keyboard that's drawn under the board, which can be clicked
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyboardKey {
+    Letter(char),
+    Enter,
+    Backspace,
 }
 
 pub struct App {
-    pub game: Game,
+    // one board, unless several are being solved at once
+    pub session: Session,
     pub input: String,
     pub disclaimer: Option<Disclaimer>,
     pub should_quit: bool,
     pub theme: Theme,
+    pub tutorial: Option<TutorialStep>,
+    pub show_guess_dots: bool,
+    // what the colors mean, under the keyboard
+    pub show_legend: bool,
+    pub keyboard_rows: Vec<String>,
+    // where each key ended up the last time the keyboard was drawn, for mouse clicks
+    pub keyboard_keys: Vec<(Rect, KeyboardKey)>,
+    // the characters the words use, which can be typed even when they aren't letters
+    alphabet: Vec<char>,
+    pub restart_on_enter: bool,
+    // the word is guessed as soon as its last letter is typed, without pressing Enter
+    pub auto_submit: bool,
+    // the letters already found are typed in for the player on every new row
+    pub assist: bool,
+    // F2 shows the answer. A game where it was shown doesn't count towards the stats
+    pub cheats: bool,
+    pub cheated: bool,
+    pub friend_grid: Option<Vec<Vec<HitAccuracy>>>,
+    pub matched_friend_path: bool,
+    pub idle_hint_ticks: Option<u32>,
+    pub assist_level: AssistLevel,
+    // the board stays empty, and any key exits
+    pub daily_done: bool,
+    // in practice mode, how many ticks a result stays up before the next game starts by itself
+    pub auto_restart_ticks: Option<u32>,
+    // totals for every game played, including earlier runs when there is a stats file
+    pub stats: Stats,
+    stats_path: Option<PathBuf>,
+    saved_game_path: Option<PathBuf>,
+    // the rules and controls are drawn over the board while this is set
+    pub show_help: bool,
+    // the same for a list of what every letter on the keyboard is known to be, for checking the
+    // colors
+    pub show_letter_states: bool,
+    // a first word to try, which the welcome message suggests
+    pub opener: Option<String>,
+    // Esc was pressed during a game, and the header asks whether to really quit
+    pub confirm_quit: bool,
+    // How many cells of the newest row have their colors so far. The row turns over one cell
+    // per tick after a guess, and keys are ignored until it's done
+    pub revealed_cells: Option<usize>,
+    // when the last guess was turned down. The row is drawn in the error color until it's cleared
+    pub error_flash: Option<Instant>,
+    pub started_at: Instant,
+    // how long the game took, once it is over
+    pub solve_time: Option<Duration>,
+    idle_ticks: u32,
+    // how long the result has been up, for auto_restart_ticks
+    game_over_ticks: u32,
+    // counts down while the welcome message is up, if it goes away by itself
+    welcome_ticks: Option<u32>,
+    // the answers of the games played since we started, so a new random answer isn't one of
+    // them. Once every word has come up it starts over
+    played_answers: Vec<String>,
+    game_config: GameOptions,
+}
+
+// What the end screen shows once a game is won or lost
+#[derive(Debug, PartialEq)]
+pub struct EndScreen {
+    pub won: bool,
+    pub guesses_used: usize,
+    pub max_guesses: usize,
+    // one for every board
+    pub answers: Vec<String>,
+    pub solve_time: Duration,
 }
 
 pub struct AppOptions {
     pub theme: Theme,
     pub game_config: GameOptions,
+    pub tutorial: bool,
+    // how many words are solved at once, from the same guesses
+    pub boards: usize,
+    pub show_guess_dots: bool,
+    pub show_legend: bool,
+    pub keyboard_rows: Vec<String>,
+    pub restart_on_enter: bool,
+    pub auto_submit: bool,
+    pub assist: bool,
+    pub cheats: bool,
+    pub friend_grid: Option<Vec<Vec<HitAccuracy>>>,
+    // when set, a hint is offered after the player spends this many ticks on one row
+    pub idle_hint_ticks: Option<u32>,
+    // which of the hints and suggested words are offered
+    pub assist_level: AssistLevel,
+    // when set, every game is followed by a new one this many ticks after it ends
+    pub auto_restart_ticks: Option<u32>,
+    // the welcome message can be left out, or cleared this many ticks after it's shown
+    pub show_welcome: bool,
+    pub welcome_ticks: Option<u32>,
+    // where the stats are loaded from and saved to. Without one they only last until we exit
+    pub stats_path: Option<PathBuf>,
+    // where an unfinished game is saved when the player quits, so it can be resumed later
+    pub saved_game_path: Option<PathBuf>,
+    // a saved game to carry on with, instead of starting a new one
+    pub resumed_game: Option<Game>,
+}
+
+impl Default for AppOptions {
+    fn default() -> Self {
+        AppOptions {
+            theme: Theme::default(),
+            game_config: GameOptions::default(),
+            tutorial: false,
+            boards: 1,
+            show_guess_dots: false,
+            show_legend: false,
+            keyboard_rows: english_keyboard_rows(),
+            restart_on_enter: false,
+            auto_submit: false,
+            assist: false,
+            cheats: false,
+            friend_grid: None,
+            idle_hint_ticks: None,
+            assist_level: AssistLevel::Candidates,
+            auto_restart_ticks: None,
+            show_welcome: true,
+            welcome_ticks: None,
+            stats_path: None,
+            saved_game_path: None,
+            resumed_game: None,
+        }
+    }
+}
+
+fn key_at(keys: &[(Rect, KeyboardKey)], column: u16, row: u16) -> Option<KeyboardKey> {
+    keys.iter()
+        .find(|(rect, _)| {
+            (rect.left()..rect.right()).contains(&column)
+                && (rect.top()..rect.bottom()).contains(&row)
+        })
+        .map(|(_, key)| *key)
+}
+
+// the arrangement of the on-screen keyboard, to match the one the player types on. The
+// colors don't depend on it, only where each letter is drawn
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyboardLayout {
+    Qwerty,
+    Azerty,
+    // the punctuation on the top row is left out, since it can't be typed into a guess
+    Dvorak,
+}
+
+impl KeyboardLayout {
+    pub fn rows(self) -> Vec<String> {
+        let rows = match self {
+            KeyboardLayout::Qwerty => ["qwertyuiop", "asdfghjkl", "zxcvbnm"],
+            KeyboardLayout::Azerty => ["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            KeyboardLayout::Dvorak => ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"],
+        };
+        rows.into_iter().map(String::from).collect()
+    }
+}
+
+pub fn english_keyboard_rows() -> Vec<String> {
+    KeyboardLayout::Qwerty.rows()
+}
+
+const KEYBOARD_ROW_LENGTH: usize = 10;
+
+// A list of the player's own words can use letters the layout doesn't have, like accents,
+// apostrophes or another alphabet altogether. Rows without any of its letters are left out, and
+// the letters still missing go in order on rows of their own at the bottom
+fn fit_keyboard_rows(rows: Vec<String>, alphabet: &[char]) -> Vec<String> {
+    let mut rows: Vec<String> = rows
+        .into_iter()
+        .filter(|row| row.chars().any(|c| alphabet.contains(&c)))
+        .collect();
+    let missing: Vec<char> = alphabet
+        .iter()
+        .filter(|c| !rows.iter().any(|row| row.contains(**c)))
+        .copied()
+        .collect();
+    rows.extend(
+        missing
+            .chunks(KEYBOARD_ROW_LENGTH)
+            .map(|chunk| chunk.iter().collect()),
+    );
+    rows
 }
 
 impl App {
-    pub fn new(args: AppOptions) -> Self {
-        App {
-            game: Game::new(args.game_config),
+    pub fn new(args: AppOptions) -> Result<Self, GameError> {
+        let (first_game_config, tutorial) = match args.tutorial {
+            true => (
+                GameOptions {
+                    answer: Some(TUTORIAL_ANSWER.to_string()),
+                    word_length: TUTORIAL_ANSWER.chars().count(),
+                    ..args.game_config.clone()
+                },
+                Some(TutorialStep::Introduction),
+            ),
+            false => (args.game_config.clone(), None),
+        };
+        let stats = args
+            .stats_path
+            .as_deref()
+            .map(Stats::load)
+            .unwrap_or_default();
+        // the saved stats already have today's game in them, and it mustn't be counted twice
+        let daily_done = tutorial.is_none()
+            && args.resumed_game.is_none()
+            && args.game_config.daily.is_some()
+            && stats.last_daily == args.game_config.daily;
+
+        let disclaimer = match (tutorial, &args.resumed_game) {
+            _ if daily_done => Some(Disclaimer::DailyDoneMessage),
+            (Some(step), _) => Some(Disclaimer::TutorialMessage(step)),
+            // a replayed game is already over, so it opens on its result
+            (None, Some(game)) => Some(match game.game_status() {
+                GameStatus::Won => Disclaimer::GameWonMessage,
+                GameStatus::Lost => {
+                    Disclaimer::GameOverMessage(game.get_answer().unwrap_or_default())
+                }
+                GameStatus::InProgress => Disclaimer::ResumedGameMessage,
+            }),
+            (None, None) => args.show_welcome.then_some(Disclaimer::WelcomeMessage),
+        };
+        let session = match args.resumed_game {
+            Some(game) => Session::resume(game),
+            None => Session::new(first_game_config, args.boards)?,
+        };
+
+        // worked out once, because they go through the whole dictionary
+        let opener = (args.assist_level >= AssistLevel::Candidates)
+            .then(|| session.boards()[0].opener())
+            .filter(|word| !word.is_empty());
+        let alphabet = session.boards()[0].alphabet();
+        // the layout is only changed for a list of the player's own, since the built-in words
+        // are all on it
+        let keyboard_rows = match args.game_config.dictionary_path {
+            Some(_) => fit_keyboard_rows(args.keyboard_rows, &alphabet),
+            None => args.keyboard_rows,
+        };
+
+        Ok(App {
+            opener,
+            session,
             input: String::from(""),
-            disclaimer: Some(Disclaimer::WelcomeMessage),
+            disclaimer,
             should_quit: false,
             theme: args.theme,
+            tutorial,
+            show_guess_dots: args.show_guess_dots,
+            show_legend: args.show_legend,
+            keyboard_rows,
+            keyboard_keys: vec![],
+            alphabet,
+            restart_on_enter: args.restart_on_enter,
+            auto_submit: args.auto_submit,
+            assist: args.assist,
+            cheats: args.cheats,
+            cheated: false,
+            matched_friend_path: args.friend_grid.is_some(),
+            friend_grid: args.friend_grid,
+            idle_hint_ticks: args.idle_hint_ticks,
+            assist_level: args.assist_level,
+            daily_done,
+            auto_restart_ticks: args.auto_restart_ticks,
+            stats,
+            stats_path: args.stats_path,
+            saved_game_path: args.saved_game_path,
+            show_help: false,
+            show_letter_states: false,
+            confirm_quit: false,
+            revealed_cells: None,
+            error_flash: None,
+            started_at: Instant::now(),
+            // there's no game to time
+            solve_time: daily_done.then_some(Duration::ZERO),
+            idle_ticks: 0,
+            game_over_ticks: 0,
+            welcome_ticks: args.welcome_ticks,
+            played_answers: vec![],
+            game_config: args.game_config,
+        })
+    }
+
+    // starts over with the same options, keeping the stats. The tutorial is only ever played once
+    pub fn restart(&mut self) {
+        if !self.can_restart() {
+            return;
+        }
+
+        // a seed is meant to give the same puzzle every time, so only random answers move on
+        if self.game_config.seed.is_none() {
+            for answer in self.session.answers().unwrap_or_default() {
+                // the answer was picked again, so every word had had its turn
+                if self.played_answers.contains(&answer) {
+                    self.played_answers.clear();
+                }
+                self.played_answers.push(answer);
+            }
+        }
+        let options = GameOptions {
+            played_answers: self.played_answers.clone(),
+            ..self.game_config.clone()
+        };
+        // these are the options the first game was made with, so this only fails if that did
+        self.session = match Session::new(options, self.session.boards().len()) {
+            Ok(session) => session,
+            Err(_) => return,
+        };
+        self.started_at = Instant::now();
+        self.solve_time = None;
+        self.input = String::from("");
+        self.disclaimer = Some(Disclaimer::NewGameMessage);
+        self.tutorial = None;
+        self.matched_friend_path = self.friend_grid.is_some();
+        self.idle_ticks = 0;
+        self.game_over_ticks = 0;
+        self.revealed_cells = None;
+        self.error_flash = None;
+        self.cheated = false;
+    }
+
+    // the daily puzzle would only come round again, so once today's is done there's nothing to
+    // play until tomorrow
+    pub fn can_restart(&self) -> bool {
+        self.game_config.daily.is_none() || self.stats.last_daily != self.game_config.daily
+    }
+
+    // the first board, which is the only one unless several are being solved at once
+    pub fn game(&self) -> &Game {
+        &self.session.boards()[0]
+    }
+
+    // only there while the game is over, and drawn over the board until a new game starts
+    pub fn end_screen(&self) -> Option<EndScreen> {
+        if self.session.game_status() == GameStatus::InProgress {
+            return None;
+        }
+
+        Some(EndScreen {
+            won: self.session.game_status() == GameStatus::Won,
+            guesses_used: self.session.guesses_used(),
+            max_guesses: self.session.max_guesses(),
+            // there are none when they're kept hidden after a loss
+            answers: self.session.answers().unwrap_or_default(),
+            solve_time: self.elapsed(),
+        })
+    }
+
+    // every letter on the keyboard in alphabetical order, and the best it has done so far
+    pub fn letter_states(&self) -> Vec<(char, Option<HitAccuracy>)> {
+        let mut letters: Vec<char> = self
+            .keyboard_rows
+            .iter()
+            .flat_map(|row| row.chars())
+            .collect();
+        letters.sort_unstable();
+        letters.dedup();
+        letters
+            .into_iter()
+            .map(|letter| (letter, self.session.get_letter_match_state(letter)))
+            .collect()
+    }
+
+    // the clock stops when the game ends
+    pub fn elapsed(&self) -> Duration {
+        self.solve_time.unwrap_or_else(|| self.started_at.elapsed())
+    }
+
+    pub fn on_tick(&mut self) {
+        if self.daily_done {
+            return;
+        }
+        self.reveal_next_cell();
+        if self
+            .error_flash
+            .is_some_and(|at| at.elapsed() >= ERROR_FLASH)
+        {
+            self.error_flash = None;
+        }
+
+        if self.disclaimer == Some(Disclaimer::WelcomeMessage) {
+            if let Some(ticks) = &mut self.welcome_ticks {
+                *ticks = ticks.saturating_sub(1);
+                if *ticks == 0 {
+                    self.disclaimer = None;
+                }
+            }
+        }
+
+        // the clock for the next practice game starts once the last row has turned over
+        if let Some(wait) = self.auto_restart_ticks {
+            if self.session.game_status() != GameStatus::InProgress && self.revealed_cells.is_none()
+            {
+                self.game_over_ticks += 1;
+                if self.game_over_ticks >= wait {
+                    self.restart();
+                }
+                return;
+            }
+        }
+
+        let threshold = match self.idle_hint_ticks {
+            // the hints talk about one word, so they're only offered with a single board
+            Some(threshold)
+                if self.session.game_status() == GameStatus::InProgress
+                    && self.session.boards().len() == 1
+                    && self.assist_level >= AssistLevel::Hints =>
+            {
+                threshold
+            }
+            _ => return,
+        };
+
+        self.idle_ticks += 1;
+        if self.idle_ticks == threshold {
+            self.disclaimer = Some(Disclaimer::IdleHintMessage(self.idle_hint()));
+        } else if self.idle_ticks == 2 * threshold {
+            // still stuck, so we let the solver pick a word. We wait for the first guess, because
+            // before that every word in the dictionary is still possible and ranking them is slow
+            if let Some(hint) = self.solver_hint() {
+                self.disclaimer = Some(Disclaimer::IdleHintMessage(hint));
+            }
         }
     }
 
-    pub fn on_key(&mut self, key: KeyEvent) -> () {
-        if self.game.game_status() != GameStatus::InProgress {
+    fn reveal_next_cell(&mut self) {
+        if let Some(cells) = self.revealed_cells {
+            let cells = cells + 1;
+            self.revealed_cells = (cells < self.game().word_length()).then_some(cells);
+        }
+    }
+
+    fn solver_hint(&self) -> Option<String> {
+        if self.game().guesses().is_empty() || self.assist_level < AssistLevel::Candidates {
+            return None;
+        }
+
+        let candidates = consistent_words(self.game());
+        let (best, _) = suggest(self.game(), &candidates).into_iter().next()?;
+        Some(match candidates.len() {
+            1 => format!("Still stuck? Only one word fits: try '{best}'."),
+            count => format!("Still stuck? {count} words fit the clues. Try '{best}'."),
+        })
+    }
+
+    fn idle_hint(&self) -> String {
+        let constraints = self.game().constraints();
+        let found: String = constraints.min_counts.keys().collect();
+        let excluded: String = constraints.excluded.iter().collect();
+
+        match (found.is_empty(), excluded.is_empty()) {
+            (true, true) => String::from(
+                "Need a hint? Common letters like e, a, r, s and t are a good place to start.",
+            ),
+            (true, false) => {
+                format!("Need a hint? Try letters you haven't used yet, and avoid '{excluded}'.")
+            }
+            (false, _) => format!("Need a hint? Your next word should use '{found}'."),
+        }
+    }
+
+    pub fn on_key(&mut self, key: KeyEvent) {
+        if self.daily_done {
             self.should_quit = true;
             return;
         }
 
-        match key.code {
-            KeyCode::Esc => {
+        if self.revealed_cells.is_some() {
+            return;
+        }
+
+        // the help covers the board, so nothing reaches the game until it's closed
+        if self.show_help {
+            if matches!(key.code, KeyCode::Char('?') | KeyCode::Esc) {
+                self.show_help = false;
+            }
+            return;
+        }
+
+        if self.show_letter_states {
+            if matches!(key.code, KeyCode::Char('!') | KeyCode::Esc) {
+                self.show_letter_states = false;
+            }
+            return;
+        }
+
+        if self.cheats && key.code == KeyCode::F(2) {
+            self.on_answer_peeked();
+            return;
+        }
+
+        if self.session.game_status() != GameStatus::InProgress {
+            self.on_game_over_key(key.code);
+            return;
+        }
+
+        // only y quits, so a stray Esc doesn't lose the game. Any other key carries on
+        if self.confirm_quit {
+            self.confirm_quit = false;
+            if key.code == KeyCode::Char('y') {
+                self.save_game();
                 self.should_quit = true;
             }
+            return;
+        }
+
+        if key.code == KeyCode::Char('g') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.on_give_up();
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.confirm_quit = true,
             KeyCode::Backspace => self.on_backspace(),
             KeyCode::Enter => self.on_enter_press(),
+            KeyCode::Tab => self.on_hint_requested(),
+            KeyCode::Char('?') => self.show_help = true,
+            KeyCode::Char('!') => self.show_letter_states = true,
+            KeyCode::Up => self.on_review(true),
+            KeyCode::Down => self.on_review(false),
             KeyCode::Char(letter) => self.on_letter_entered(letter),
             _ => (),
         };
     }
 
-    pub fn on_valid_word(&mut self) -> () {
+    // the row being looked at again, if any
+    pub fn reviewed_row(&self) -> Option<usize> {
+        match self.disclaimer {
+            Some(Disclaimer::ReviewedRowMessage(row)) => Some(row),
+            _ => None,
+        }
+    }
+
+    // Up starts from the newest guess and goes back to the first. Down goes forward again, and
+    // past the newest guess stops reviewing
+    fn on_review(&mut self, up: bool) {
+        let guessed = self.game().guesses().len();
+        if guessed == 0 {
+            return;
+        }
+
+        let row = match (self.reviewed_row(), up) {
+            (None, true) => Some(guessed - 1),
+            (None, false) => return,
+            (Some(row), true) => Some(row.saturating_sub(1)),
+            (Some(row), false) => Some(row + 1).filter(|row| *row < guessed),
+        };
+        self.disclaimer = row.map(Disclaimer::ReviewedRowMessage);
+    }
+
+    // a click on the keyboard is the same as typing that key
+    pub fn on_click(&mut self, column: u16, row: u16) {
+        let code = match key_at(&self.keyboard_keys, column, row) {
+            Some(KeyboardKey::Letter(letter)) => KeyCode::Char(letter),
+            Some(KeyboardKey::Enter) => KeyCode::Enter,
+            Some(KeyboardKey::Backspace) => KeyCode::Backspace,
+            None => return,
+        };
+        self.on_key(KeyEvent::from(code));
+    }
+
+    // 'r' plays again, unless today's daily puzzle is done. Otherwise any key quits, unless we
+    // were asked to restart on Enter or we're practicing, in which case only Esc quits
+    fn on_game_over_key(&mut self, key: KeyCode) {
+        match (
+            key,
+            self.restart_on_enter || self.auto_restart_ticks.is_some(),
+        ) {
+            (KeyCode::Char('r'), _) | (KeyCode::Enter, true) if self.can_restart() => {
+                self.restart()
+            }
+            (KeyCode::Esc, true) | (_, false) => self.should_quit = true,
+            _ => (),
+        }
+    }
+
+    pub fn on_valid_word(&mut self) {
         self.disclaimer = None;
         self.input = String::from("");
+        self.idle_ticks = 0;
+        self.fill_known_letters();
+
+        // the tutorial explains one more thing after every word that is played
+        if let Some(step) = self.tutorial {
+            let next_step = step.next();
+            self.tutorial = Some(next_step);
+            self.disclaimer = Some(Disclaimer::TutorialMessage(next_step));
+        }
+
+        if self.friend_grid.is_some() {
+            let row = self.game().guesses().len();
+            self.disclaimer = Some(Disclaimer::FriendRowMessage(row, self.matches_friend_row()));
+        }
+    }
+
+    // whether the last guess got the same colors as the friend's grid did on that row
+    fn matches_friend_row(&self) -> bool {
+        let row = self.game().guesses().len();
+        match (&self.friend_grid, self.game().last_guess()) {
+            (Some(grid), Some(guess)) => grid.get(row - 1) == Some(&guess.accuracies()),
+            _ => false,
+        }
+    }
+
+    // The row is typed left to right, so with --assist the known letters right after what's been
+    // typed are filled in, and the next letter typed goes to the first position still unknown
+    fn fill_known_letters(&mut self) {
+        if !self.assist {
+            return;
+        }
+
+        let known = self.game().known_letters();
+        let letters = known
+            .iter()
+            .skip(self.input.chars().count())
+            .map_while(|letter| *letter);
+        self.input.extend(letters);
     }
 
-    pub fn on_backspace(&mut self) -> () {
+    pub fn on_backspace(&mut self) {
         let _ = self.input.pop();
-        ()
     }
 
-    pub fn on_letter_entered(&mut self, letter: char) -> () {
-        if self.input.chars().count() <= 4 {
-            self.input.push(letter);
+    pub fn on_hint_requested(&mut self) {
+        if self.assist_level < AssistLevel::Hints {
+            return;
+        }
+        if let Some((index, letter)) = self.session.hint() {
+            self.disclaimer = Some(Disclaimer::LetterHintMessage(index + 1, letter));
+        }
+    }
+
+    // the dictionary is all lowercase, so Shift and Caps Lock shouldn't matter. Digits and
+    // punctuation are ignored, unless they're part of the words, like the ' in "don't"
+    pub fn on_letter_entered(&mut self, letter: char) {
+        let in_words = letter.is_alphabetic() || self.alphabet.contains(&letter);
+        // there's no row to type into once the game is over
+        if !in_words || self.session.game_status() != GameStatus::InProgress {
+            return;
+        }
+
+        // a few capitals lowercase to more than one character, like İ, and only fit whole
+        let lowercase: Vec<char> = letter.to_lowercase().collect();
+        if self.input.chars().count() + lowercase.len() <= self.game().word_length() {
+            self.input.extend(lowercase);
+            self.fill_known_letters();
+
+            // only a letter that fills the row submits it. A word that's turned down stays
+            // there to be fixed with Backspace, and retyping the last letter tries again
+            if self.auto_submit && self.input.chars().count() == self.game().word_length() {
+                self.on_enter_press();
+            }
+        }
+    }
+
+    // Counts as a loss, and shows the answer the same way running out of guesses does
+    fn on_give_up(&mut self) {
+        self.session.give_up();
+        if let Some(game_result) = self.session.result() {
+            self.on_game_finished(game_result);
+        }
+
+        self.input.clear();
+        let answers = self.session.answers().unwrap_or_default();
+        self.disclaimer = Some(Disclaimer::GameOverMessage(answers.join(", ")));
+    }
+
+    fn on_answer_peeked(&mut self) {
+        let answers: Option<Vec<&str>> = self
+            .session
+            .boards()
+            .iter()
+            .map(|game| game.peek_answer())
+            .collect();
+        self.disclaimer = Some(Disclaimer::CheatAnswerMessage(
+            answers.map(|answers| answers.join(", ")),
+        ));
+        if self.session.game_status() == GameStatus::InProgress {
+            self.cheated = true;
+        }
+    }
+
+    // Only a single board is saved, and never the tutorial or a game where the answer was looked
+    // up, since a resumed game counts. Like the stats, a failed save shouldn't stop the player
+    // from quitting
+    fn save_game(&self) {
+        if let (Some(path), [game], None, false) = (
+            &self.saved_game_path,
+            self.session.boards(),
+            self.tutorial,
+            self.cheated,
+        ) {
+            let _ = game.snapshot().save(path);
         }
     }
 
-    pub fn on_enter_press(&mut self) -> () {
+    fn on_game_finished(&mut self, game_result: GameResult) {
+        self.solve_time = Some(self.started_at.elapsed());
+
+        // the tutorial is practice, so it doesn't count towards the stats
+        if self.tutorial.is_some() {
+            return;
+        }
+
+        // a finished game has nothing left to resume
+        if let Some(path) = &self.saved_game_path {
+            let _ = std::fs::remove_file(path);
+        }
+
+        // and neither does a game where the answer was looked up
+        if self.cheated {
+            return;
+        }
+
+        self.stats.record(GameResult {
+            solve_time: self.solve_time,
+            ..game_result
+        });
+        if self.game_config.daily.is_some() {
+            self.stats.last_daily = self.game_config.daily;
+        }
+
+        // there's nowhere to show an error while the board is up, and failing to save the
+        // stats shouldn't stop the game
+        if let Some(path) = &self.stats_path {
+            let _ = self.stats.save(path);
+        }
+    }
+
+    pub fn on_enter_press(&mut self) {
         // clear the disclaimer the first time a word is played
         if self.disclaimer == Some(Disclaimer::WelcomeMessage) {
             self.disclaimer = None;
         }
 
-        if &self.input.chars().count() != &5 {
-            return ();
+        // a word that's too short is turned down before it gets to the game
+        if self.input.chars().count() != self.game().word_length() {
+            self.error_flash = Some(Instant::now());
+            return;
+        }
+
+        let (status, result) = self.session.guess(&self.input);
+        if result == GuessResult::Valid {
+            self.revealed_cells = Some(0);
+            if !self.matches_friend_row() {
+                self.matched_friend_path = false;
+            }
         }
 
-        match self.game.guess(&self.input) {
+        // only the guess that ends the game is valid, anything after it is GameIsAlreadyOver
+        if let (Some(game_result), GuessResult::Valid) = (self.session.result(), result) {
+            self.on_game_finished(game_result);
+        }
+
+        // the winning or losing word is on the board now, and nothing more can be typed
+        if status != GameStatus::InProgress {
+            self.input.clear();
+        }
+
+        match (status, result) {
             (GameStatus::Lost, _) => {
-                if let Ok(answer) = self.game.get_answer() {
-                    self.disclaimer = Some(Disclaimer::GameOverMessage(answer.to_string()));
-                }
+                let answers = self.session.answers().unwrap_or_default();
+                self.disclaimer = Some(Disclaimer::GameOverMessage(answers.join(", ")));
             }
             (GameStatus::Won, _) => {
                 self.disclaimer = Some(Disclaimer::GameWonMessage);
@@ -89,11 +829,1392 @@ impl App {
             (_, word_res) => match word_res {
                 GuessResult::Valid => {
                     let _ = &self.on_valid_word();
+
+                    // a solved board doesn't get the guess, so its warning is from an older one
+                    let warning = self
+                        .session
+                        .boards()
+                        .iter()
+                        .filter(|board| board.game_status() == GameStatus::InProgress)
+                        .find_map(Game::hard_mode_warning);
+                    if let Some(warning) = warning {
+                        self.disclaimer = Some(Disclaimer::HardModeWarning(warning));
+                    }
+
+                    if self.session.is_stuck() {
+                        self.disclaimer = Some(Disclaimer::NoValidGuessesMessage);
+                    }
                 }
+                // Valid is handled above, so there's never an empty MoveFeedback in the header
                 result => {
                     self.disclaimer = Some(Disclaimer::MoveFeedback(result));
+                    self.error_flash = Some(Instant::now());
                 }
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{GameDifficulty, GameSnapshot};
+    use chrono::NaiveDate;
+    use std::path::Path;
+
+    // The answer is 'slump' in most of these tests, and the options are whatever else the test
+    // needs
+    fn slump_app(options: AppOptions) -> App {
+        App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..options.game_config
+            },
+            ..options
+        })
+        .unwrap()
+    }
+
+    fn tutorial_app() -> App {
+        App::new(AppOptions {
+            tutorial: true,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    // the tiles turning over is tested on its own, so the other tests skip straight past it
+    fn play(app: &mut App, word: &str) {
+        app.input = word.to_string();
+        app.on_enter_press();
+        app.revealed_cells = None;
+    }
+
+    fn finished_app(restart_on_enter: bool) -> App {
+        let mut app = slump_app(AppOptions {
+            restart_on_enter,
+            ..Default::default()
+        });
+        play(&mut app, "slump");
+        app
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn test_ctrl_g_gives_up_and_counts_as_a_loss() {
+        let mut app = slump_app(AppOptions::default());
+        play(&mut app, "crane");
+        app.input = String::from("sl");
+
+        // a plain g is still typed
+        app.on_key(key(KeyCode::Char('g')));
+        assert_eq!(app.input, "slg");
+
+        app.on_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+        assert_eq!(app.session.game_status(), GameStatus::Lost);
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::GameOverMessage("slump".to_string()))
+        );
+        assert_eq!(app.input, "");
+        assert_eq!(app.stats.games_played, 1);
+        assert_eq!(app.stats.wins, 0);
+
+        let end_screen = app.end_screen().unwrap();
+        assert!(!end_screen.won);
+        assert_eq!(end_screen.answers, ["slump"]);
+    }
+
+    #[test]
+    fn test_the_arrow_keys_do_nothing_before_the_first_guess() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        for code in [KeyCode::Up, KeyCode::Down] {
+            app.on_key(key(code));
+            assert_eq!(app.reviewed_row(), None);
+            assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+        }
+    }
+
+    #[test]
+    fn test_the_arrow_keys_stay_within_the_guesses() {
+        let mut app = slump_app(AppOptions::default());
+        play(&mut app, "crane");
+        play(&mut app, "plumb");
+
+        let mut rows = vec![];
+        for code in [KeyCode::Up, KeyCode::Up, KeyCode::Up, KeyCode::Down] {
+            app.on_key(key(code));
+            rows.push(app.reviewed_row());
+        }
+        assert_eq!(rows, [Some(1), Some(0), Some(0), Some(1)]);
+
+        // past the newest guess is back to typing
+        app.on_key(key(KeyCode::Down));
+        assert_eq!(app.reviewed_row(), None);
+        app.on_key(key(KeyCode::Down));
+        assert_eq!(app.reviewed_row(), None);
+
+        // and looking doesn't change the game
+        app.on_key(key(KeyCode::Up));
+        assert_eq!(app.session.guesses_used(), 2);
+        app.input = "slump".to_string();
+        app.on_key(key(KeyCode::Enter));
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_loss_only_says_game_over_when_the_answer_is_hidden() {
+        for reveal_on_loss in [true, false] {
+            let mut app = slump_app(AppOptions {
+                game_config: GameOptions {
+                    max_guesses: 1,
+                    reveal_on_loss,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+            play(&mut app, "crane");
+
+            let (message, answers) = match reveal_on_loss {
+                true => ("slump", vec!["slump".to_string()]),
+                false => ("", vec![]),
+            };
+            assert_eq!(
+                app.disclaimer,
+                Some(Disclaimer::GameOverMessage(message.to_string()))
+            );
+            assert_eq!(app.end_screen().unwrap().answers, answers);
+        }
+    }
+
+    #[test]
+    fn test_stats_are_kept_across_games() {
+        let mut app = finished_app(true);
+        app.on_key(key(KeyCode::Enter));
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            play(&mut app, word);
+        }
+
+        assert_eq!(app.stats.games_played, 2);
+        assert_eq!(app.stats.wins, 1);
+        assert_eq!(app.stats.current_streak, 0);
+        assert_eq!(app.stats.max_streak, 1);
+        assert_eq!(app.stats.distribution, vec![1]);
+    }
+
+    #[test]
+    fn test_stats_are_saved_when_a_game_ends_and_loaded_on_start() {
+        let path = std::env::temp_dir()
+            .join(format!("wordlet-app-test-{}", std::process::id()))
+            .join("stats.json");
+        let stats_app = || {
+            slump_app(AppOptions {
+                stats_path: Some(path.clone()),
+                ..Default::default()
+            })
+        };
+
+        let mut app = stats_app();
+        play(&mut app, "slump");
+
+        let app = stats_app();
+        assert_eq!(app.stats.games_played, 1);
+        assert_eq!(app.stats.current_streak, 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_a_finished_daily_puzzle_is_not_restarted_on_the_same_day() {
+        let path = std::env::temp_dir()
+            .join(format!("wordlet-app-test-{}", std::process::id()))
+            .join("daily-stats.json");
+        let daily_app = |day| {
+            App::new(AppOptions {
+                game_config: GameOptions {
+                    daily: Some(day),
+                    ..Default::default()
+                },
+                stats_path: Some(path.clone()),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        let give_up = |app: &mut App| {
+            app.on_key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL));
+            app.revealed_cells = None;
+        };
+        let today = NaiveDate::from_ymd(2022, 1, 28);
+
+        let mut app = daily_app(today);
+        assert!(app.can_restart());
+        give_up(&mut app);
+        assert!(!app.can_restart());
+        app.on_key(key(KeyCode::Char('r')));
+        assert_eq!(app.session.game_status(), GameStatus::Lost);
+        assert!(app.should_quit);
+
+        // the day is saved with the stats, so starting it up again doesn't help
+        let mut app = daily_app(today);
+        assert!(app.daily_done);
+        assert_eq!(app.disclaimer, Some(Disclaimer::DailyDoneMessage));
+        app.on_key(key(KeyCode::Char('s')));
+        assert!(app.game().guesses().is_empty());
+        assert!(app.should_quit);
+        assert_eq!(app.stats.games_played, 1);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_the_clock_stops_when_the_game_ends() {
+        let mut app = finished_app(false);
+        let solve_time = app.solve_time.expect("the game is over");
+        std::thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(app.elapsed(), solve_time);
+        assert!(app.stats.best_time.is_some());
+
+        // playing on after the end doesn't count the game twice
+        play(&mut app, "slump");
+        assert_eq!(app.stats.games_played, 1);
+    }
+
+    #[test]
+    fn test_winning_shows_the_end_screen_with_the_guess_count() {
+        let mut app = slump_app(AppOptions::default());
+        assert_eq!(app.end_screen(), None);
+
+        play(&mut app, "plump");
+        play(&mut app, "slump");
+        let end_screen = app.end_screen().expect("the game is won");
+
+        assert!(end_screen.won);
+        assert_eq!(end_screen.guesses_used, 2);
+        assert_eq!(end_screen.max_guesses, 6);
+        assert_eq!(end_screen.answers, vec!["slump"]);
+        assert_eq!(Some(end_screen.solve_time), app.solve_time);
+    }
+
+    #[test]
+    fn test_losing_shows_the_answer_on_the_end_screen() {
+        let mut app = slump_app(AppOptions::default());
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            play(&mut app, word);
+        }
+        let end_screen = app.end_screen().expect("the game is lost");
+
+        assert!(!end_screen.won);
+        assert_eq!(end_screen.guesses_used, 6);
+        assert_eq!(end_screen.answers, vec!["slump"]);
+
+        app.on_key(key(KeyCode::Char('r')));
+        assert_eq!(app.end_screen(), None);
+    }
+
+    fn saved_game_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("wordlet-app-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    fn saving_app(path: &Path, resumed_game: Option<Game>) -> App {
+        slump_app(AppOptions {
+            saved_game_path: Some(path.to_path_buf()),
+            resumed_game,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_a_replayed_game_opens_on_its_result() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("crane");
+        game.guess("plumb");
+        let replayed = Game::from_replay(&game.to_replay().unwrap()).unwrap();
+
+        let app = App::new(AppOptions {
+            resumed_game: Some(replayed),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::GameOverMessage("slump".to_string()))
+        );
+        assert_eq!(app.game().guesses(), game.guesses());
+        assert!(app.end_screen().is_some());
+    }
+
+    #[test]
+    fn test_a_game_quit_part_way_through_can_be_resumed_and_won() {
+        let path = saved_game_path("resume.json");
+        let mut app = saving_app(&path, None);
+        play(&mut app, "clamp");
+        play(&mut app, "plump");
+        app.on_key(key(KeyCode::Esc));
+        app.on_key(key(KeyCode::Char('y')));
+        assert!(app.should_quit);
+
+        let snapshot = GameSnapshot::load(&path).expect("the game was saved");
+        let game = Game::restore(&snapshot).unwrap();
+        let mut resumed = saving_app(&path, Some(game));
+
+        assert_eq!(resumed.disclaimer, Some(Disclaimer::ResumedGameMessage));
+        assert_eq!(resumed.game().guesses(), app.game().guesses());
+        assert_eq!(resumed.game().row_states(), app.game().row_states());
+        assert_eq!(resumed.session.guesses_used(), 2);
+        for letter in "clampuz".chars() {
+            assert_eq!(
+                resumed.session.get_letter_match_state(letter),
+                app.session.get_letter_match_state(letter)
+            );
+        }
+
+        play(&mut resumed, "slump");
+        assert_eq!(resumed.session.game_status(), GameStatus::Won);
+        assert_eq!(resumed.end_screen().unwrap().guesses_used, 3);
+        // there's nothing left to resume once the game is over
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_only_unfinished_games_are_saved() {
+        let path = saved_game_path("finished.json");
+        let mut app = saving_app(&path, None);
+        play(&mut app, "slump");
+        app.on_key(key(KeyCode::Esc));
+        assert!(!path.exists());
+
+        let mut tutorial = App::new(AppOptions {
+            tutorial: true,
+            saved_game_path: Some(path.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+        tutorial.on_key(key(KeyCode::Esc));
+        tutorial.on_key(key(KeyCode::Char('y')));
+        assert!(tutorial.should_quit);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_the_tutorial_does_not_count_towards_the_stats() {
+        let mut app = tutorial_app();
+        play(&mut app, TUTORIAL_ANSWER);
+        assert_eq!(app.stats.games_played, 0);
+    }
+
+    #[test]
+    fn test_any_key_exits_the_game_over_screen_by_default() {
+        let mut app = finished_app(false);
+        app.on_key(key(KeyCode::Enter));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_enter_starts_a_new_game_on_the_game_over_screen() {
+        let mut app = finished_app(true);
+        app.on_key(key(KeyCode::Enter));
+
+        assert!(!app.should_quit);
+        assert_eq!(app.session.game_status(), GameStatus::InProgress);
+        assert!(app.game().guesses().is_empty());
+        assert_eq!(app.disclaimer, Some(Disclaimer::NewGameMessage));
+    }
+
+    #[test]
+    fn test_r_starts_a_new_game_on_the_game_over_screen_by_default() {
+        let mut app = finished_app(false);
+        app.on_key(key(KeyCode::Char('r')));
+
+        assert!(!app.should_quit);
+        assert_eq!(app.session.game_status(), GameStatus::InProgress);
+    }
+
+    #[test]
+    fn test_restarting_after_a_win_is_a_fresh_game() {
+        let mut app = finished_app(false);
+        app.input = String::from("sl");
+        app.restart();
+
+        assert_eq!(app.session.game_status(), GameStatus::InProgress);
+        assert!(app.game().guesses().is_empty());
+        assert_eq!(app.input, "");
+        assert_eq!(app.stats.games_played, 1);
+    }
+
+    fn practice_app() -> App {
+        slump_app(AppOptions {
+            auto_restart_ticks: Some(3),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_practice_starts_a_new_game_a_moment_after_one_ends() {
+        let mut app = practice_app();
+        play(&mut app, "slump");
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        app.on_tick();
+
+        assert_eq!(app.session.game_status(), GameStatus::InProgress);
+        assert!(app.game().guesses().is_empty());
+        assert_eq!(app.stats.games_played, 1);
+        assert!(!app.should_quit);
+
+        // the wait starts over for the next game
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            play(&mut app, word);
+        }
+        app.on_tick();
+        assert!(app.end_screen().is_some());
+    }
+
+    #[test]
+    fn test_practice_waits_for_the_last_row_to_turn_over() {
+        let mut app = practice_app();
+        app.input = String::from("slump");
+        app.on_enter_press();
+
+        for _ in 0..5 {
+            app.on_tick();
+        }
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_only_esc_stops_practice() {
+        let mut app = practice_app();
+        play(&mut app, "slump");
+
+        app.on_key(key(KeyCode::Char('x')));
+        assert!(!app.should_quit);
+        app.on_key(key(KeyCode::Esc));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_the_keyboard_gets_the_letters_its_layout_is_missing() {
+        let alphabet: Vec<char> = "'-abcdefghijklmnopqrstuvwxyzé".chars().collect();
+        let rows = fit_keyboard_rows(english_keyboard_rows(), &alphabet);
+        assert_eq!(rows[..3], english_keyboard_rows()[..]);
+        assert_eq!(rows[3], "'-é");
+
+        // a layout with none of the letters is no use at all
+        let alphabet: Vec<char> = "абвгдежзийклм".chars().collect();
+        assert_eq!(
+            fit_keyboard_rows(english_keyboard_rows(), &alphabet),
+            ["абвгдежзий", "клм"]
+        );
+    }
+
+    // like main, the player's words are read once and every game is played with them
+    fn own_words(path: &Path) -> GameOptions {
+        let options = GameOptions {
+            dictionary_path: Some(path.to_path_buf()),
+            ..Default::default()
+        };
+        GameOptions {
+            words: Some(options.validate().unwrap()),
+            ..options
+        }
+    }
+
+    #[test]
+    fn test_characters_from_the_words_can_be_typed() {
+        let path = std::env::temp_dir().join(format!("wordlet-hyphens-{}.txt", std::process::id()));
+        std::fs::write(&path, "don't\nx-ray\n").unwrap();
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("x-ray".to_string()),
+                ..own_words(&path)
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(app.keyboard_rows.last().unwrap(), "'-");
+
+        for c in "don't,".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.input, "don't");
+
+        // the built-in words don't use them, so they're still ignored there
+        let mut app = idle_app();
+        app.on_key(key(KeyCode::Char('-')));
+        assert_eq!(app.input, "");
+    }
+
+    #[test]
+    fn test_answers_do_not_repeat_until_every_word_has_come_up() {
+        let path = std::env::temp_dir().join(format!("wordlet-pool-{}.txt", std::process::id()));
+        std::fs::write(&path, "crane\nslump\nadieu\n").unwrap();
+        let mut app = App::new(AppOptions {
+            game_config: own_words(&path),
+            restart_on_enter: true,
+            ..Default::default()
+        })
+        .unwrap();
+        // the words were read once, so a new game doesn't need the file
+        let _ = std::fs::remove_file(&path);
+
+        let mut answers = vec![];
+        for _ in 0..9 {
+            let answer = app.game().peek_answer().unwrap().to_string();
+            play(&mut app, &answer);
+            answers.push(answer);
+            app.restart();
+        }
+
+        // every word comes up once before any of them comes up again, and never twice in a row
+        for cycle in answers.chunks(3) {
+            let mut cycle = cycle.to_vec();
+            cycle.sort();
+            assert_eq!(cycle, ["adieu", "crane", "slump"]);
+        }
+        for window in answers.windows(2) {
+            assert_ne!(window[0], window[1]);
+        }
+    }
+
+    #[test]
+    fn test_a_seeded_game_replays_the_same_answer() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                seed: Some(7),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let answer = app.game().peek_answer().unwrap().to_string();
+        play(&mut app, &answer);
+        app.restart();
+        assert_eq!(app.game().peek_answer(), Some(answer.as_str()));
+    }
+
+    #[test]
+    fn test_the_welcome_suggests_a_first_word() {
+        let mut app = idle_app();
+        let opener = app.opener.clone().unwrap();
+        assert_eq!(opener, app.game().opener());
+        // it can be played
+        play(&mut app, &opener);
+        assert_eq!(app.session.guesses_used(), 1);
+    }
+
+    #[test]
+    fn test_the_welcome_can_be_left_out() {
+        let app = App::new(AppOptions {
+            show_welcome: false,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(app.disclaimer, None);
+
+        // the message that says what's going on in the tutorial is still shown
+        let tutorial = App::new(AppOptions {
+            show_welcome: false,
+            tutorial: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(
+            tutorial.disclaimer,
+            Some(Disclaimer::TutorialMessage(_))
+        ));
+    }
+
+    #[test]
+    fn test_the_welcome_goes_away_by_itself() {
+        let mut app = App::new(AppOptions {
+            welcome_ticks: Some(2),
+            ..Default::default()
+        })
+        .unwrap();
+        app.on_tick();
+        assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+        app.on_tick();
+        assert_eq!(app.disclaimer, None);
+
+        // other messages stay until they're replaced
+        let mut app = App::new(AppOptions {
+            welcome_ticks: Some(1),
+            ..Default::default()
+        })
+        .unwrap();
+        app.disclaimer = Some(Disclaimer::NewGameMessage);
+        app.on_tick();
+        assert_eq!(app.disclaimer, Some(Disclaimer::NewGameMessage));
+    }
+
+    #[test]
+    fn test_esc_asks_before_quitting_a_game() {
+        let mut app = idle_app();
+        play(&mut app, "slept");
+        app.on_key(key(KeyCode::Esc));
+        assert!(app.confirm_quit);
+        assert!(!app.should_quit);
+
+        app.on_key(key(KeyCode::Char('y')));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_any_other_key_carries_on_with_the_game() {
+        let mut app = idle_app();
+        app.input = String::from("sl");
+        app.on_key(key(KeyCode::Esc));
+        app.on_key(key(KeyCode::Char('n')));
+        assert!(!app.confirm_quit);
+        assert!(!app.should_quit);
+        // the key only answered the question, so it isn't typed
+        assert_eq!(app.input, "sl");
+
+        app.on_key(key(KeyCode::Esc));
+        app.on_key(key(KeyCode::Esc));
+        assert!(!app.should_quit);
+        app.on_key(key(KeyCode::Char('u')));
+        assert_eq!(app.input, "slu");
+    }
+
+    fn auto_submit_app() -> App {
+        slump_app(AppOptions {
+            auto_submit: true,
+            ..Default::default()
+        })
+    }
+
+    fn assist_app() -> App {
+        slump_app(AppOptions {
+            assist: true,
+            ..Default::default()
+        })
+    }
+
+    fn cheats_app() -> App {
+        slump_app(AppOptions {
+            cheats: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_f2_shows_the_answer_only_with_cheats() {
+        let mut app = idle_app();
+        app.on_key(key(KeyCode::F(2)));
+        assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+        assert!(!app.cheated);
+
+        let mut app = cheats_app();
+        app.on_key(key(KeyCode::F(2)));
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::CheatAnswerMessage(Some(String::from("slump"))))
+        );
+        assert!(app.cheated);
+    }
+
+    #[test]
+    fn test_a_game_where_the_answer_was_shown_is_not_counted() {
+        let mut app = cheats_app();
+        app.on_key(key(KeyCode::F(2)));
+        play(&mut app, "slump");
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        assert_eq!(app.stats.games_played, 0);
+
+        // the next game is counted again
+        app.restart();
+        assert!(!app.cheated);
+        let answer = app.game().peek_answer().unwrap().to_string();
+        play(&mut app, &answer);
+        assert_eq!(app.stats.games_played, 1);
+    }
+
+    #[test]
+    fn test_a_game_where_the_answer_was_shown_is_not_saved() {
+        let path = saved_game_path("cheated.json");
+        let mut app = saving_app(&path, None);
+        app.cheats = true;
+        play(&mut app, "clamp");
+        app.on_key(key(KeyCode::F(2)));
+        app.on_key(key(KeyCode::Esc));
+        app.on_key(key(KeyCode::Char('y')));
+        assert!(app.should_quit);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_auto_submit_guesses_the_word_when_the_last_letter_is_typed() {
+        let mut app = auto_submit_app();
+        for c in "slum".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.session.guesses_used(), 0);
+
+        app.on_key(key(KeyCode::Char('p')));
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        assert_eq!(app.session.guesses_used(), 1);
+    }
+
+    #[test]
+    fn test_assist_starts_the_next_row_with_the_letters_found() {
+        let mut app = assist_app();
+        assert_eq!(app.input, "");
+
+        play(&mut app, "stare");
+        assert_eq!(app.input, "s");
+
+        app.on_key(key(KeyCode::Backspace));
+        app.on_key(key(KeyCode::Char('c')));
+        assert_eq!(app.input, "c");
+    }
+
+    #[test]
+    fn test_assist_skips_over_letters_found_further_along() {
+        let mut app = assist_app();
+        play(&mut app, "shunt");
+        assert_eq!(app.input, "s");
+
+        app.on_key(key(KeyCode::Char('l')));
+        assert_eq!(app.input, "slu");
+        app.on_key(key(KeyCode::Char('m')));
+        app.on_key(key(KeyCode::Char('p')));
+        app.on_key(key(KeyCode::Enter));
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_auto_submit_leaves_a_rejected_word_to_be_fixed() {
+        let mut app = auto_submit_app();
+        for c in "sluzz".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.session.guesses_used(), 0);
+        assert_eq!(app.input, "sluzz");
+
+        app.on_key(key(KeyCode::Backspace));
+        app.on_key(key(KeyCode::Backspace));
+        app.on_key(key(KeyCode::Char('m')));
+        assert_eq!(app.session.guesses_used(), 0);
+        app.on_key(key(KeyCode::Char('p')));
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_guided_mode_warns_about_a_guess_hard_mode_would_turn_down() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("abbey".to_string()),
+                difficulty: GameDifficulty::Guided,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        play(&mut app, "sleep");
+        assert_eq!(app.disclaimer, None);
+
+        play(&mut app, "hours");
+        assert_eq!(app.session.guesses_used(), 2);
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::HardModeWarning(
+                GuessResult::LetterDoesNotMatch('e', 4)
+            ))
+        );
+
+        play(&mut app, "cadet");
+        assert_eq!(app.disclaimer, None);
+    }
+
+    #[test]
+    fn test_a_valid_guess_leaves_the_header_empty() {
+        let mut app = idle_app();
+        assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+        app.input = String::from("slept");
+        app.on_enter_press();
+        assert_eq!(app.disclaimer, None);
+
+        // a guess after a turned down one clears the feedback too
+        app.input = String::from("zzzzz");
+        app.on_enter_press();
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::MoveFeedback(GuessResult::NotInDictionary))
+        );
+        app.input = String::from("plumb");
+        app.on_enter_press();
+        assert_eq!(app.disclaimer, None);
+    }
+
+    #[test]
+    fn test_nothing_can_be_typed_after_a_win() {
+        let mut app = idle_app();
+        app.input = String::from("slump");
+        app.on_enter_press();
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        assert_eq!(app.input, "");
+
+        app.on_letter_entered('a');
+        assert_eq!(app.input, "");
+
+        // with restart_on_enter the letters don't quit, and they're still not typed
+        let mut app = slump_app(AppOptions {
+            restart_on_enter: true,
+            ..Default::default()
+        });
+        play(&mut app, "slump");
+        for c in "abc".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert!(!app.should_quit);
+        assert_eq!(app.input, "");
+    }
+
+    #[test]
+    fn test_esc_quits_on_the_game_over_screen() {
+        let mut app = finished_app(true);
+        app.on_key(key(KeyCode::Esc));
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_other_keys_are_ignored_on_the_game_over_screen() {
+        let mut app = finished_app(true);
+        app.on_key(key(KeyCode::Char('a')));
+        assert!(!app.should_quit);
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_new_game_after_the_tutorial_is_not_a_tutorial() {
+        let mut app = tutorial_app();
+        app.restart();
+        assert_eq!(app.tutorial, None);
+    }
+
+    fn friend_app() -> App {
+        use HitAccuracy::*;
+        slump_app(AppOptions {
+            // the friend played 'slept' and then 'slump'
+            friend_grid: Some(vec![
+                vec![InRightPlace, InRightPlace, NotInWord, InWord, NotInWord],
+                vec![InRightPlace; 5],
+            ]),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_matching_a_friends_row_is_reported() {
+        let mut app = friend_app();
+        play(&mut app, "sleep");
+        assert_eq!(app.disclaimer, Some(Disclaimer::FriendRowMessage(1, false)));
+        assert!(!app.matched_friend_path);
+    }
+
+    #[test]
+    fn test_following_a_friends_path_is_tracked_until_the_end() {
+        let mut app = friend_app();
+        play(&mut app, "slept");
+        assert_eq!(app.disclaimer, Some(Disclaimer::FriendRowMessage(1, true)));
+
+        play(&mut app, "slump");
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        assert!(app.matched_friend_path);
+    }
+
+    fn idle_options() -> AppOptions {
+        AppOptions {
+            idle_hint_ticks: Some(3),
+            ..Default::default()
+        }
+    }
+
+    fn idle_app() -> App {
+        slump_app(idle_options())
+    }
+
+    #[test]
+    fn test_a_hint_is_offered_after_idling_on_a_row() {
+        let mut app = idle_app();
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+
+        app.on_tick();
+        assert!(matches!(
+            app.disclaimer,
+            Some(Disclaimer::IdleHintMessage(_))
+        ));
+    }
+
+    #[test]
+    fn test_the_idle_timer_restarts_on_every_row() {
+        let mut app = idle_app();
+        app.on_tick();
+        app.on_tick();
+        play(&mut app, "slept");
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(app.disclaimer, None);
+
+        app.on_tick();
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::IdleHintMessage(String::from(
+                "Need a hint? Your next word should use 'lps'."
+            )))
+        );
+    }
+
+    #[test]
+    fn test_the_solver_suggests_a_word_after_idling_twice_as_long() {
+        let mut app = idle_app();
+        play(&mut app, "admit");
+        play(&mut app, "slept");
+        for _ in 0..6 {
+            app.on_tick();
+        }
+
+        match &app.disclaimer {
+            Some(Disclaimer::IdleHintMessage(hint)) => assert!(hint.starts_with("Still stuck?")),
+            other => panic!("expected a solver hint, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_the_solver_waits_for_the_first_guess() {
+        let mut app = idle_app();
+        for _ in 0..6 {
+            app.on_tick();
+        }
+        assert!(matches!(
+            &app.disclaimer,
+            Some(Disclaimer::IdleHintMessage(hint)) if hint.starts_with("Need a hint?")
+        ));
+    }
+
+    #[test]
+    fn test_tab_reveals_a_letter() {
+        let mut app = idle_app();
+        app.input = String::from("sl");
+        app.on_key(key(KeyCode::Tab));
+
+        assert_eq!(app.disclaimer, Some(Disclaimer::LetterHintMessage(1, 's')));
+        assert_eq!(app.input, "sl");
+    }
+
+    // whether the opener is suggested, whether Tab reveals a letter, whether idling gives a hint
+    // about the letters, and whether idling for longer suggests a word
+    fn assists_offered(assist_level: AssistLevel) -> (bool, bool, bool, bool) {
+        let mut app = slump_app(AppOptions {
+            assist_level,
+            ..idle_options()
+        });
+        let opener = app.opener.is_some();
+
+        play(&mut app, "admit");
+        play(&mut app, "slept");
+        app.on_key(key(KeyCode::Tab));
+        let letter = matches!(app.disclaimer, Some(Disclaimer::LetterHintMessage(..)));
+
+        let hint_starts_with = |app: &App, start: &str| match &app.disclaimer {
+            Some(Disclaimer::IdleHintMessage(hint)) => hint.starts_with(start),
+            _ => false,
+        };
+        for _ in 0..3 {
+            app.on_tick();
+        }
+        let idle = hint_starts_with(&app, "Need a hint?");
+        for _ in 0..3 {
+            app.on_tick();
+        }
+        let solver = hint_starts_with(&app, "Still stuck?");
+
+        (opener, letter, idle, solver)
+    }
+
+    #[test]
+    fn test_assist_level_0_offers_no_help() {
+        assert_eq!(
+            assists_offered(AssistLevel::Off),
+            (false, false, false, false)
+        );
+    }
+
+    #[test]
+    fn test_assist_level_1_offers_hints_but_no_words() {
+        assert_eq!(
+            assists_offered(AssistLevel::Hints),
+            (false, true, true, false)
+        );
+    }
+
+    #[test]
+    fn test_assist_level_2_suggests_words_too() {
+        assert_eq!(
+            assists_offered(AssistLevel::Candidates),
+            (true, true, true, true)
+        );
+        assert_eq!(AppOptions::default().assist_level, AssistLevel::Candidates);
+    }
+
+    fn type_word(app: &mut App, word: &str) {
+        for c in word.chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        app.on_key(key(KeyCode::Enter));
+        app.revealed_cells = None;
+    }
+
+    #[test]
+    fn test_capital_letters_are_guessed_like_lowercase_ones() {
+        let mut upper = idle_app();
+        let mut lower = idle_app();
+        type_word(&mut upper, "SLEPT");
+        type_word(&mut lower, "slept");
+
+        assert_eq!(upper.game().guesses(), lower.game().guesses());
+        assert_eq!(upper.disclaimer, lower.disclaimer);
+
+        type_word(&mut upper, "SLuMP");
+        assert_eq!(upper.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_guess_turns_over_one_cell_per_tick() {
+        let mut app = idle_app();
+        app.input = String::from("slept");
+        app.on_enter_press();
+        assert_eq!(app.revealed_cells, Some(0));
+
+        // keys wait until the row is done
+        app.on_key(key(KeyCode::Char('s')));
+        assert_eq!(app.input, "");
+
+        for cells in 1..5 {
+            app.on_tick();
+            assert_eq!(app.revealed_cells, Some(cells));
+        }
+        app.on_tick();
+        assert_eq!(app.revealed_cells, None);
+
+        app.on_key(key(KeyCode::Char('s')));
+        assert_eq!(app.input, "s");
+    }
+
+    #[test]
+    fn test_a_rejected_guess_has_nothing_to_turn_over() {
+        let mut app = idle_app();
+        app.input = String::from("zzzzz");
+        app.on_enter_press();
+        assert_eq!(app.revealed_cells, None);
+    }
+
+    #[test]
+    fn test_a_rejected_guess_flashes_the_row() {
+        let mut app = idle_app();
+        app.input = String::from("zzzzz");
+        app.on_enter_press();
+        assert!(app.error_flash.is_some());
+
+        // it's still there a tick later, and gone once it has been up long enough
+        app.on_tick();
+        assert!(app.error_flash.is_some());
+        app.error_flash = Instant::now().checked_sub(ERROR_FLASH);
+        app.on_tick();
+        assert_eq!(app.error_flash, None);
+
+        app.input = String::from("sl");
+        app.on_enter_press();
+        assert!(app.error_flash.is_some());
+    }
+
+    #[test]
+    fn test_a_valid_guess_does_not_flash() {
+        let mut app = idle_app();
+        play(&mut app, "slept");
+        assert_eq!(app.error_flash, None);
+    }
+
+    #[test]
+    fn test_every_keyboard_layout_has_every_letter_once() {
+        for layout in [
+            KeyboardLayout::Qwerty,
+            KeyboardLayout::Azerty,
+            KeyboardLayout::Dvorak,
+        ] {
+            let mut letters: Vec<char> = layout.rows().concat().chars().collect();
+            letters.sort_unstable();
+            assert_eq!(
+                letters.into_iter().collect::<String>(),
+                "abcdefghijklmnopqrstuvwxyz",
+                "{layout:?}"
+            );
+        }
+        assert_eq!(KeyboardLayout::Azerty.rows()[0], "azertyuiop");
+        assert_eq!(KeyboardLayout::Dvorak.rows()[1], "aoeuidhtns");
+    }
+
+    #[test]
+    fn test_the_keyboard_layout_only_moves_the_letters() {
+        let mut app = slump_app(AppOptions {
+            keyboard_rows: KeyboardLayout::Azerty.rows(),
+            ..Default::default()
+        });
+        play(&mut app, "slept");
+        let qwerty = {
+            let mut app = idle_app();
+            play(&mut app, "slept");
+            app.letter_states()
+        };
+        assert_eq!(app.letter_states(), qwerty);
+    }
+
+    #[test]
+    fn test_the_letter_states_are_the_keyboard_colors() {
+        let mut app = idle_app();
+        play(&mut app, "slept");
+        play(&mut app, "plumb");
+
+        let states = app.letter_states();
+        assert_eq!(states.len(), 26);
+        assert_eq!(states[0], ('a', None));
+        for (letter, state) in &states {
+            assert_eq!(*state, app.game().get_letter_match_state(*letter));
+        }
+        let state = |letter: char| states.iter().find(|(l, _)| *l == letter).unwrap().1;
+        assert_eq!(state('s'), Some(HitAccuracy::InRightPlace));
+        // p was in the wrong spot in slept, and is also in the wrong spot in plumb
+        assert_eq!(state('p'), Some(HitAccuracy::InWord));
+        assert_eq!(state('t'), Some(HitAccuracy::NotInWord));
+    }
+
+    #[test]
+    fn test_the_letter_states_open_and_close_without_typing() {
+        let mut app = idle_app();
+        app.on_key(key(KeyCode::Char('!')));
+        assert!(app.show_letter_states);
+
+        app.on_key(key(KeyCode::Char('s')));
+        assert_eq!(app.input, "");
+        app.on_key(key(KeyCode::Char('!')));
+        assert!(!app.show_letter_states);
+
+        app.on_key(key(KeyCode::Char('!')));
+        app.on_key(key(KeyCode::Esc));
+        assert!(!app.show_letter_states);
+        assert!(!app.should_quit);
+    }
+
+    #[test]
+    fn test_digits_and_punctuation_are_not_typed() {
+        let mut app = idle_app();
+        for c in ['s', '5', ',', 'l', ' '] {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.input, "sl");
+
+        // so a word with symbols mixed in is still the word, rather than "Not a valid word!"
+        for c in "u3m#p".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.input, "slump");
+        app.on_key(key(KeyCode::Enter));
+        app.revealed_cells = None;
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+
+    #[test]
+    fn test_typing_stops_at_the_word_length() {
+        for (answer, typed) in [("slump", "slumpsy"), ("planet", "planetsy")] {
+            let mut app = App::new(AppOptions {
+                game_config: GameOptions {
+                    answer: Some(answer.to_string()),
+                    word_length: answer.len(),
+                    skip_dictionary_check: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .unwrap();
+
+            // a paste arrives as one key after another
+            for c in typed.chars() {
+                app.on_key(key(KeyCode::Char(c)));
+            }
+            assert_eq!(app.input, answer);
+
+            app.on_key(key(KeyCode::Backspace));
+            app.on_key(key(KeyCode::Char('x')));
+            assert_eq!(app.input.chars().count(), answer.len());
+            app.on_key(key(KeyCode::Char('y')));
+            assert_eq!(app.input.chars().count(), answer.len());
+        }
+    }
+
+    #[test]
+    fn test_a_capital_that_lowercases_to_two_characters_only_fits_whole() {
+        let mut app = idle_app();
+        for c in "slum".chars() {
+            app.on_key(key(KeyCode::Char(c)));
+        }
+        app.on_key(key(KeyCode::Char('İ')));
+        assert_eq!(app.input, "slum");
+
+        app.input = String::from("slu");
+        app.on_key(key(KeyCode::Char('İ')));
+        assert_eq!(app.input.chars().count(), 5);
+    }
+
+    #[test]
+    fn test_the_help_opens_and_closes_without_touching_the_game() {
+        let mut app = idle_app();
+        play(&mut app, "slept");
+        app.input = String::from("sl");
+
+        app.on_key(key(KeyCode::Char('?')));
+        assert!(app.show_help);
+        app.on_key(key(KeyCode::Char('?')));
+        assert!(!app.show_help);
+
+        app.on_key(key(KeyCode::Char('?')));
+        app.on_key(key(KeyCode::Esc));
+        assert!(!app.show_help);
+        assert!(!app.should_quit);
+
+        assert_eq!(app.input, "sl");
+        assert_eq!(app.game().guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_keys_are_ignored_while_the_help_is_open() {
+        let mut app = idle_app();
+        app.input = String::from("slum");
+        app.on_key(key(KeyCode::Char('?')));
+        for code in [
+            KeyCode::Char('p'),
+            KeyCode::Backspace,
+            KeyCode::Enter,
+            KeyCode::Tab,
+        ] {
+            app.on_key(key(code));
+        }
+
+        assert_eq!(app.input, "slum");
+        assert!(app.game().guesses().is_empty());
+        assert_eq!(app.session.hint(), Some((0, 's')));
+    }
+
+    #[test]
+    fn test_idle_hints_are_off_by_default() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        for _ in 0..1000 {
+            app.on_tick();
+        }
+        assert_eq!(app.disclaimer, Some(Disclaimer::WelcomeMessage));
+    }
+
+    #[test]
+    fn test_tutorial_starts_with_the_introduction() {
+        let app = tutorial_app();
+        assert_eq!(app.tutorial, Some(TutorialStep::Introduction));
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::TutorialMessage(TutorialStep::Introduction))
+        );
+    }
+
+    #[test]
+    fn test_tutorial_advances_after_each_valid_guess() {
+        let mut app = tutorial_app();
+        play(&mut app, "slept");
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::TutorialMessage(TutorialStep::RightPlace))
+        );
+
+        play(&mut app, "alive");
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::TutorialMessage(TutorialStep::InWord))
+        );
+        assert_eq!(app.tutorial, Some(TutorialStep::InWord));
+    }
+
+    #[test]
+    fn test_tutorial_does_not_advance_on_an_invalid_guess() {
+        let mut app = tutorial_app();
+        play(&mut app, "djkle");
+        assert_eq!(app.tutorial, Some(TutorialStep::Introduction));
+        assert_eq!(
+            app.disclaimer,
+            Some(Disclaimer::MoveFeedback(GuessResult::NotInDictionary))
+        );
+    }
+
+    #[test]
+    fn test_tutorial_can_be_won_with_the_fixed_answer() {
+        let mut app = tutorial_app();
+        play(&mut app, TUTORIAL_ANSWER);
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+        assert_eq!(app.disclaimer, Some(Disclaimer::GameWonMessage));
+    }
+
+    #[test]
+    fn test_a_click_is_matched_to_the_key_under_it() {
+        let keys = vec![
+            (Rect::new(10, 2, 1, 1), KeyboardKey::Letter('q')),
+            (Rect::new(12, 2, 1, 1), KeyboardKey::Letter('w')),
+            (Rect::new(10, 4, 1, 1), KeyboardKey::Enter),
+        ];
+        assert_eq!(key_at(&keys, 10, 2), Some(KeyboardKey::Letter('q')));
+        assert_eq!(key_at(&keys, 12, 2), Some(KeyboardKey::Letter('w')));
+        assert_eq!(key_at(&keys, 10, 4), Some(KeyboardKey::Enter));
+        // the gaps between keys and the space around them aren't keys
+        assert_eq!(key_at(&keys, 11, 2), None);
+        assert_eq!(key_at(&keys, 10, 3), None);
+        assert_eq!(key_at(&keys, 0, 0), None);
+    }
+
+    #[test]
+    fn test_clicking_the_keyboard_plays_a_word() {
+        let mut app = slump_app(AppOptions::default());
+        app.keyboard_keys = "slumpx"
+            .chars()
+            .enumerate()
+            .map(|(i, letter)| (Rect::new(i as u16, 0, 1, 1), KeyboardKey::Letter(letter)))
+            .chain([
+                (Rect::new(0, 1, 1, 1), KeyboardKey::Enter),
+                (Rect::new(1, 1, 1, 1), KeyboardKey::Backspace),
+            ])
+            .collect();
+
+        for column in [0, 1, 2, 3, 5] {
+            app.on_click(column, 0);
+        }
+        assert_eq!(app.input, "slumx");
+        app.on_click(1, 1);
+        app.on_click(4, 0);
+        app.on_click(9, 9);
+        assert_eq!(app.input, "slump");
+
+        app.on_click(0, 1);
+        assert_eq!(app.session.game_status(), GameStatus::Won);
+    }
+}
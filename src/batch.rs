@@ -0,0 +1,527 @@
+use crate::engine::{json_string, Game, GameStatus, GuessResult, HitAccuracy};
+use std::io::{self, BufRead, Write};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    // for screen readers and logs: sentences and words instead of codes, for --plain
+    Plain,
+}
+
+// Plays a game without the terminal UI, for bots and scripts. Every line of input is a guess,
+// and every guess gets one line back, e.g. "crane in_progress valid .YG.." where G is in the
+// right place, Y is in the word and . is not. A guess that isn't played gets "-" for its
+// colors. Once the game is over the rest of the input is ignored, and a last line sums it up:
+// "summary won 3/6", or "summary lost X/6 slump" with the answer unless it's kept hidden.
+//
+// With Format::Json every line is a JSON object instead, so scripts don't have to parse the
+// text. See json_guess_line and json_summary_line for what's in them.
+//
+// Format::Plain is for people rather than scripts. It starts with a line explaining the
+// rules, and every letter of a guess is spelled out, e.g. "crane: c=absent r=right ...".
+pub fn run(
+    mut game: Game,
+    format: Format,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<()> {
+    if format == Format::Plain {
+        writeln!(output, "{}", plain_intro_line(&game))?;
+    }
+
+    for line in input.lines() {
+        let guess = line?.trim().to_lowercase();
+        if guess.is_empty() {
+            continue;
+        }
+
+        let (status, result) = game.guess(&guess);
+        let line = match format {
+            Format::Text => text_guess_line(&game, &guess, status, result),
+            Format::Json => json_guess_line(&game, &guess, status, result),
+            Format::Plain => plain_guess_line(&game, &guess, result),
+        };
+        writeln!(output, "{line}")?;
+
+        if status != GameStatus::InProgress {
+            break;
+        }
+    }
+
+    let line = match format {
+        Format::Text => text_summary_line(&game),
+        Format::Json => json_summary_line(&game),
+        Format::Plain => plain_summary_line(&game),
+    };
+    writeln!(output, "{line}")
+}
+
+fn text_guess_line(game: &Game, guess: &str, status: GameStatus, result: GuessResult) -> String {
+    let colors = match (result, game.last_guess()) {
+        (GuessResult::Valid, Some(row)) => row.accuracies().into_iter().map(color).collect(),
+        _ => String::from("-"),
+    };
+    format!("{guess} {} {} {colors}", status.name(), result_name(result))
+}
+
+fn text_summary_line(game: &Game) -> String {
+    let used = game.guesses_used();
+    let max = game.max_guesses();
+    match game.game_status() {
+        GameStatus::Won => format!("summary won {used}/{max}"),
+        GameStatus::Lost => match game.get_answer() {
+            Ok(answer) => format!("summary lost X/{max} {answer}"),
+            Err(_) => format!("summary lost X/{max}"),
+        },
+        GameStatus::InProgress => format!("summary in_progress {used}/{max}"),
+    }
+}
+
+// {"word":"crane","status":"InProgress","result":"Valid","letters":[{"letter":"c",
+// "accuracy":"NotInWord"},...]}. The names are the ones in the engine's enums. A guess that
+// isn't played has no letters, and a rule it broke adds "letter" and maybe "position"
+fn json_guess_line(game: &Game, guess: &str, status: GameStatus, result: GuessResult) -> String {
+    let letters: Vec<String> = match (result, game.last_guess()) {
+        (GuessResult::Valid, Some(row)) => row
+            .letters()
+            .iter()
+            .map(|gl| {
+                format!(
+                    "{{\"letter\":{},\"accuracy\":\"{:?}\"}}",
+                    json_string(&gl.letter.to_string()),
+                    gl.accuracy
+                )
+            })
+            .collect(),
+        _ => vec![],
+    };
+
+    let (name, letter, position) = match result {
+        GuessResult::DoesNotIncludeRequiredLetter(letter) => {
+            ("DoesNotIncludeRequiredLetter", Some(letter), None)
+        }
+        GuessResult::LetterDoesNotMatch(letter, position) => {
+            ("LetterDoesNotMatch", Some(letter), Some(position))
+        }
+        GuessResult::UsesEliminatedLetter(letter) => ("UsesEliminatedLetter", Some(letter), None),
+        GuessResult::DuplicateGuess => ("DuplicateGuess", None, None),
+        GuessResult::GameIsAlreadyOver => ("GameIsAlreadyOver", None, None),
+        GuessResult::IncorrectCharacterCount => ("IncorrectCharacterCount", None, None),
+        GuessResult::NotInDictionary => ("NotInDictionary", None, None),
+        GuessResult::Valid => ("Valid", None, None),
+    };
+    let letter = letter.map_or_else(String::new, |l| {
+        format!(",\"letter\":{}", json_string(&l.to_string()))
+    });
+    let position = position.map_or_else(String::new, |p| format!(",\"position\":{p}"));
+
+    format!(
+        "{{\"word\":{},\"status\":\"{status:?}\",\"result\":\"{name}\"{letter}{position},\"letters\":[{}]}}",
+        json_string(guess),
+        letters.join(",")
+    )
+}
+
+// {"summary":true,"status":"Lost","guesses_used":6,"max_guesses":6,"answer":"slump"}. The
+// answer is null unless the game was lost, and it wasn't kept hidden
+fn json_summary_line(game: &Game) -> String {
+    let answer = match game.get_answer() {
+        Ok(answer) => json_string(&answer),
+        Err(_) => String::from("null"),
+    };
+    format!(
+        "{{\"summary\":true,\"status\":\"{:?}\",\"guesses_used\":{},\"max_guesses\":{},\"answer\":{answer}}}",
+        game.game_status(),
+        game.guesses_used(),
+        game.max_guesses()
+    )
+}
+
+fn plain_intro_line(game: &Game) -> String {
+    format!(
+        "Guess the {} letter word in {} tries, one guess per line. Every letter comes back as \
+         right if it's in the right spot, word if it's in the word but somewhere else, or absent.",
+        game.word_length(),
+        game.max_guesses()
+    )
+}
+
+fn plain_guess_line(game: &Game, guess: &str, result: GuessResult) -> String {
+    let feedback = match (result, game.last_guess()) {
+        (GuessResult::Valid, Some(row)) => row
+            .letters()
+            .iter()
+            .map(|gl| format!("{}={}", gl.letter, accuracy_word(gl.accuracy)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        (GuessResult::DoesNotIncludeRequiredLetter(letter), _) => {
+            format!("not played, it has to use '{letter}'")
+        }
+        (GuessResult::LetterDoesNotMatch(letter, position), _) => {
+            format!("not played, letter {position} has to be '{letter}'")
+        }
+        (GuessResult::UsesEliminatedLetter(letter), _) => {
+            format!("not played, '{letter}' is already known not to be in the word")
+        }
+        (GuessResult::DuplicateGuess, _) => String::from("not played, you already guessed it"),
+        (GuessResult::IncorrectCharacterCount, _) => format!(
+            "not played, it has to be {} letters long",
+            game.word_length()
+        ),
+        (GuessResult::NotInDictionary, _) => String::from("not played, it isn't in the dictionary"),
+        (GuessResult::GameIsAlreadyOver, _) | (GuessResult::Valid, None) => {
+            String::from("not played, the game is already over")
+        }
+    };
+    format!("{guess}: {feedback}")
+}
+
+fn plain_summary_line(game: &Game) -> String {
+    let used = game.guesses_used();
+    let max = game.max_guesses();
+    match game.game_status() {
+        GameStatus::Won => format!("You won in {used} of {max} guesses."),
+        GameStatus::Lost => match game.get_answer() {
+            Ok(answer) => format!("You lost. The answer was '{answer}'."),
+            Err(_) => String::from("You lost."),
+        },
+        GameStatus::InProgress => {
+            format!("The game isn't over. You used {used} of {max} guesses.")
+        }
+    }
+}
+
+pub fn accuracy_word(accuracy: HitAccuracy) -> &'static str {
+    match accuracy {
+        HitAccuracy::InRightPlace => "right",
+        HitAccuracy::InWord => "word",
+        HitAccuracy::NotInWord => "absent",
+    }
+}
+
+fn color(accuracy: HitAccuracy) -> char {
+    match accuracy {
+        HitAccuracy::InRightPlace => 'G',
+        HitAccuracy::InWord => 'Y',
+        HitAccuracy::NotInWord => '.',
+    }
+}
+
+// one word, so the line can still be split on spaces. Positions count from 1, like the header
+fn result_name(result: GuessResult) -> String {
+    match result {
+        GuessResult::Valid => String::from("valid"),
+        GuessResult::DoesNotIncludeRequiredLetter(letter) => format!("missing_letter:{letter}"),
+        GuessResult::LetterDoesNotMatch(letter, position) => {
+            format!("letter_must_match:{letter}:{position}")
+        }
+        GuessResult::UsesEliminatedLetter(letter) => format!("eliminated_letter:{letter}"),
+        GuessResult::DuplicateGuess => String::from("duplicate_guess"),
+        GuessResult::GameIsAlreadyOver => String::from("game_over"),
+        GuessResult::IncorrectCharacterCount => String::from("wrong_length"),
+        GuessResult::NotInDictionary => String::from("not_in_dictionary"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{GameDifficulty, GameOptions};
+
+    fn play_as(format: Format, options: GameOptions, input: &str) -> String {
+        let mut output = vec![];
+        run(
+            Game::new(options).unwrap(),
+            format,
+            input.as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    fn play(options: GameOptions, input: &str) -> String {
+        play_as(Format::Text, options, input)
+    }
+
+    fn slump() -> GameOptions {
+        GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_a_winning_game_is_played_line_by_line() {
+        let output = play(slump(), "admit\n\nPLUMP\nzzzzz\nslum\nslump\nafter\n");
+        assert_eq!(
+            output,
+            "admit in_progress valid ..Y..\n\
+             plump in_progress valid .GGGG\n\
+             zzzzz in_progress not_in_dictionary -\n\
+             slum in_progress wrong_length -\n\
+             slump won valid GGGGG\n\
+             summary won 3/6\n"
+        );
+    }
+
+    #[test]
+    fn test_a_lost_game_gives_the_answer() {
+        let output = play(slump(), "admit\nadorn\nadult\naffix\nafire\naging\n");
+        assert!(output.contains("aging lost valid"));
+        assert!(output.ends_with("summary lost X/6 slump\n"));
+
+        let hidden = GameOptions {
+            reveal_on_loss: false,
+            ..slump()
+        };
+        let output = play(hidden, "admit\nadorn\nadult\naffix\nafire\naging\n");
+        assert!(output.ends_with("summary lost X/6\n"));
+        assert!(!output.contains("slump"));
+    }
+
+    #[test]
+    fn test_hard_mode_rejections_name_the_rule() {
+        let output = play(
+            GameOptions {
+                difficulty: GameDifficulty::Hard,
+                ..slump()
+            },
+            "plump\ncrane\nplump",
+        );
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                "plump in_progress valid .GGGG",
+                "crane in_progress letter_must_match:l:2 -",
+                "plump in_progress duplicate_guess -",
+                "summary in_progress 1/6",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plain_output_spells_out_every_letter() {
+        let output = play_as(Format::Plain, slump(), "slept\nzzzzz\nplumb\nslump\n");
+        let lines: Vec<_> = output.lines().collect();
+        assert!(lines[0].starts_with("Guess the 5 letter word in 6 tries"));
+        assert_eq!(
+            lines[1..],
+            [
+                "slept: s=right l=right e=absent p=word t=absent",
+                "zzzzz: not played, it isn't in the dictionary",
+                "plumb: p=word l=right u=right m=right b=absent",
+                "slump: s=right l=right u=right m=right p=right",
+                "You won in 3 of 6 guesses.",
+            ]
+        );
+        // nothing in it is styled, so it reads the same in a log
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_plain_output_explains_rejections_and_losses() {
+        let output = play_as(
+            Format::Plain,
+            GameOptions {
+                difficulty: GameDifficulty::Hard,
+                max_guesses: 2,
+                ..slump()
+            },
+            "plump\ncrane\nslum\nclump\n",
+        );
+        assert_eq!(
+            output.lines().skip(1).collect::<Vec<_>>(),
+            vec![
+                "plump: p=absent l=right u=right m=right p=right",
+                "crane: not played, letter 2 has to be 'l'",
+                "slum: not played, it has to be 5 letters long",
+                "clump: c=absent l=right u=right m=right p=right",
+                "You lost. The answer was 'slump'.",
+            ]
+        );
+    }
+
+    // just enough JSON to read our own lines back, so the tests check what a script would see
+    #[derive(Debug, PartialEq)]
+    enum Json {
+        Null,
+        Bool(bool),
+        Number(usize),
+        String(String),
+        List(Vec<Json>),
+        Object(Vec<(String, Json)>),
+    }
+
+    impl Json {
+        fn get(&self, name: &str) -> Option<&Json> {
+            match self {
+                Json::Object(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+                _ => None,
+            }
+        }
+
+        fn str(&self, name: &str) -> String {
+            match self.get(name) {
+                Some(Json::String(value)) => value.clone(),
+                other => panic!("{name} is {other:?}, not a string"),
+            }
+        }
+    }
+
+    fn parse(line: &str) -> Json {
+        let mut chars = line.chars().peekable();
+        let value = parse_value(&mut chars);
+        assert_eq!(chars.next(), None, "trailing characters in {line}");
+        value
+    }
+
+    fn parse_value(chars: &mut std::iter::Peekable<std::str::Chars>) -> Json {
+        match chars.next().unwrap() {
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next().unwrap() {
+                        '"' => return Json::String(value),
+                        '\\' => match chars.next().unwrap() {
+                            'u' => {
+                                let code: String = chars.by_ref().take(4).collect();
+                                let code = u32::from_str_radix(&code, 16).unwrap();
+                                value.push(char::from_u32(code).unwrap());
+                            }
+                            c => value.push(c),
+                        },
+                        c => value.push(c),
+                    }
+                }
+            }
+            '[' => {
+                let mut items = vec![];
+                if chars.peek() == Some(&']') {
+                    chars.next();
+                    return Json::List(items);
+                }
+                loop {
+                    items.push(parse_value(chars));
+                    match chars.next().unwrap() {
+                        ',' => continue,
+                        ']' => return Json::List(items),
+                        c => panic!("unexpected {c} in a list"),
+                    }
+                }
+            }
+            '{' => {
+                let mut fields = vec![];
+                loop {
+                    let name = match parse_value(chars) {
+                        Json::String(name) => name,
+                        other => panic!("{other:?} is not a field name"),
+                    };
+                    assert_eq!(chars.next(), Some(':'));
+                    fields.push((name, parse_value(chars)));
+                    match chars.next().unwrap() {
+                        ',' => continue,
+                        '}' => return Json::Object(fields),
+                        c => panic!("unexpected {c} in an object"),
+                    }
+                }
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric()) {
+                    word.push(c);
+                }
+                match word.as_str() {
+                    "null" => Json::Null,
+                    "true" => Json::Bool(true),
+                    "false" => Json::Bool(false),
+                    number => Json::Number(number.parse().unwrap()),
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct GuessLine {
+        word: String,
+        status: String,
+        result: String,
+        letters: Vec<(String, String)>,
+    }
+
+    impl GuessLine {
+        fn from_json(json: &Json) -> GuessLine {
+            let letters = match json.get("letters") {
+                Some(Json::List(letters)) => letters
+                    .iter()
+                    .map(|letter| (letter.str("letter"), letter.str("accuracy")))
+                    .collect(),
+                other => panic!("letters is {other:?}"),
+            };
+            GuessLine {
+                word: json.str("word"),
+                status: json.str("status"),
+                result: json.str("result"),
+                letters,
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_lines_can_be_read_back() {
+        let output = play_as(Format::Json, slump(), "plump\nzzzzz\nslump\n");
+        let lines: Vec<Json> = output.lines().map(parse).collect();
+        assert_eq!(lines.len(), 4);
+
+        let accuracy = |a: &str| a.to_string();
+        assert_eq!(
+            GuessLine::from_json(&lines[0]),
+            GuessLine {
+                word: "plump".to_string(),
+                status: "InProgress".to_string(),
+                result: "Valid".to_string(),
+                letters: vec![
+                    ("p".to_string(), accuracy("NotInWord")),
+                    ("l".to_string(), accuracy("InRightPlace")),
+                    ("u".to_string(), accuracy("InRightPlace")),
+                    ("m".to_string(), accuracy("InRightPlace")),
+                    ("p".to_string(), accuracy("InRightPlace")),
+                ],
+            }
+        );
+
+        let rejected = GuessLine::from_json(&lines[1]);
+        assert_eq!(rejected.result, "NotInDictionary");
+        assert!(rejected.letters.is_empty());
+        assert_eq!(GuessLine::from_json(&lines[2]).status, "Won");
+
+        assert_eq!(lines[3].get("summary"), Some(&Json::Bool(true)));
+        assert_eq!(lines[3].str("status"), "Won");
+        assert_eq!(lines[3].get("guesses_used"), Some(&Json::Number(2)));
+        assert_eq!(lines[3].get("max_guesses"), Some(&Json::Number(6)));
+        assert_eq!(lines[3].get("answer"), Some(&Json::Null));
+    }
+
+    #[test]
+    fn test_json_rule_breaks_and_losses_carry_their_details() {
+        let output = play_as(
+            Format::Json,
+            GameOptions {
+                difficulty: GameDifficulty::Hard,
+                max_guesses: 2,
+                ..slump()
+            },
+            "plump\ncrane\nsa\"x\\\nclump\n",
+        );
+        let lines: Vec<Json> = output.lines().map(parse).collect();
+
+        assert_eq!(lines[1].str("result"), "LetterDoesNotMatch");
+        assert_eq!(lines[1].str("letter"), "l");
+        assert_eq!(lines[1].get("position"), Some(&Json::Number(2)));
+        assert_eq!(lines[2].str("word"), "sa\"x\\");
+        assert_eq!(lines[2].str("result"), "NotInDictionary");
+        assert_eq!(lines[3].str("status"), "Lost");
+        assert_eq!(lines[4].str("answer"), "slump");
+    }
+}
@@ -0,0 +1,203 @@
+use crate::engine::GameDifficulty;
+use crate::theme::ThemeName;
+use clap::ArgEnum;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// where wordlet keeps its files. We follow the XDG convention, falling back to ~/.config, or
+// %APPDATA% on windows
+pub fn config_dir() -> Option<PathBuf> {
+    let dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(dir.join("wordlet"))
+}
+
+// The defaults a player would otherwise pass on the command line every time. Flags still win
+// over anything in the file.
+#[derive(Debug, PartialEq)]
+pub struct Config {
+    pub difficulty: GameDifficulty,
+    pub theme: ThemeName,
+    pub word_length: usize,
+    pub guesses: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            difficulty: GameDifficulty::Easy,
+            theme: ThemeName::Dark,
+            word_length: 5,
+            guesses: 6,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    Unreadable(String),
+    // the line number, counting from 1
+    InvalidLine(usize),
+    UnknownKey(String),
+    InvalidValue(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Unreadable(reason) => write!(f, "the config file can't be read: {reason}"),
+            ConfigError::InvalidLine(line) => {
+                write!(
+                    f,
+                    "line {line} of the config file should look like key = value"
+                )
+            }
+            ConfigError::UnknownKey(key) => write!(f, "'{key}' is not a config setting"),
+            ConfigError::InvalidValue(key) => {
+                write!(f, "the value for '{key}' in the config file is not valid")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    // not having a config file is fine, it just means using the defaults
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Config::parse(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Unreadable(e.to_string())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+
+        for (key, value) in read_settings(contents)? {
+            let invalid = || ConfigError::InvalidValue(key.to_string());
+            match key {
+                "difficulty" => config.difficulty = parse_name(value).ok_or_else(invalid)?,
+                "theme" => config.theme = parse_name(value).ok_or_else(invalid)?,
+                "word_length" => config.word_length = value.parse().map_err(|_| invalid())?,
+                "guesses" => config.guesses = value.parse().map_err(|_| invalid())?,
+                _ => return Err(ConfigError::UnknownKey(key.to_string())),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+// Our files are TOML, but we only need plain `key = value` lines with strings and numbers, so
+// that's all we read. Comments start with #. The values are left for the caller to parse.
+pub fn read_settings(contents: &str) -> Result<Vec<(&str, &str)>, ConfigError> {
+    let mut settings = vec![];
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(ConfigError::InvalidLine(index + 1))?;
+        settings.push((key.trim(), strip_comment(value.trim())));
+    }
+
+    Ok(settings)
+}
+
+// a # after the value starts a comment, unless it's inside a string
+fn strip_comment(value: &str) -> &str {
+    if let Some(rest) = value.strip_prefix('"') {
+        if let Some(close) = rest.find('"') {
+            let (string, after) = value.split_at(close + 2);
+            if after.trim().is_empty() || after.trim_start().starts_with('#') {
+                return string;
+            }
+        }
+        return value;
+    }
+
+    value.split('#').next().unwrap_or(value).trim()
+}
+
+// the same names the flags take
+fn parse_name<T: ArgEnum>(value: &str) -> Option<T> {
+    T::from_str(&parse_string(value)?, false).ok()
+}
+
+pub fn parse_string(value: &str) -> Option<String> {
+    value.strip_prefix('"')?.strip_suffix('"').map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a_sample_config_is_read() {
+        let contents = r#"
+            # my wordlet settings
+            difficulty = "hard"
+            theme = "light"  # easier on the eyes during the day
+            word_length = 5
+            guesses = 8
+        "#;
+
+        assert_eq!(
+            Config::parse(contents),
+            Ok(Config {
+                difficulty: GameDifficulty::Hard,
+                theme: ThemeName::Light,
+                word_length: 5,
+                guesses: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn test_settings_that_are_left_out_keep_their_defaults() {
+        let config = Config::parse("theme = \"light\"").unwrap();
+        assert_eq!(config.theme, ThemeName::Light);
+        assert_eq!(config.difficulty, Config::default().difficulty);
+        assert_eq!(config.guesses, 6);
+    }
+
+    #[test]
+    fn test_mistakes_in_the_file_are_reported() {
+        assert_eq!(
+            Config::parse("difficulty"),
+            Err(ConfigError::InvalidLine(1))
+        );
+        assert_eq!(
+            Config::parse("colour = \"red\""),
+            Err(ConfigError::UnknownKey(String::from("colour")))
+        );
+        assert_eq!(
+            Config::parse("guesses = six"),
+            Err(ConfigError::InvalidValue(String::from("guesses")))
+        );
+        assert_eq!(
+            Config::parse("theme = light"),
+            Err(ConfigError::InvalidValue(String::from("theme")))
+        );
+        assert_eq!(
+            Config::parse("difficulty = \"harde\""),
+            Err(ConfigError::InvalidValue(String::from("difficulty")))
+        );
+    }
+
+    #[test]
+    fn test_a_missing_file_is_the_defaults() {
+        let path = std::env::temp_dir().join("wordlet-test-no-such-config.toml");
+        assert_eq!(Config::load(&path), Ok(Config::default()));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::engine::game_error::GameError;
+use crate::engine::utils::dictionary_list;
+use crate::engine::GameDifficulty;
+
+// A challenge code is a handful of bytes, written out with the url-safe base64 alphabet:
+// a version, the difficulty, the word length, the number of guesses allowed, the position of
+// the answer in the word list, and a checksum so a mistyped or edited code is rejected instead
+// of loading some other puzzle. Codes from before the number of guesses was stored are still
+// read, as the usual six guesses.
+const VERSION: u8 = 2;
+const VERSION_WITHOUT_GUESSES: u8 = 1;
+const DEFAULT_GUESSES: usize = 6;
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+#[derive(Debug, PartialEq)]
+pub struct Challenge {
+    pub answer: String,
+    pub difficulty: GameDifficulty,
+    pub max_guesses: usize,
+}
+
+pub fn encode(answer: &str, difficulty: GameDifficulty, max_guesses: usize) -> Option<String> {
+    let index = dictionary_list().iter().position(|w| w == answer)?;
+    let index = u16::try_from(index).ok()?;
+    let length = u8::try_from(answer.chars().count()).ok()?;
+    let guesses = u8::try_from(max_guesses).ok()?;
+    let difficulty = match difficulty {
+        GameDifficulty::Easy => 0,
+        GameDifficulty::Hard => 1,
+        GameDifficulty::Ultra => 2,
+        GameDifficulty::Guided => 3,
+        // there's no answer to share until the game is over, and by then it isn't the same game
+        GameDifficulty::Absurd => return None,
+    };
+
+    let [high, low] = index.to_be_bytes();
+    let mut bytes = vec![VERSION, difficulty, length, guesses, high, low];
+    bytes.push(checksum(&bytes));
+
+    Some(to_base64(&bytes))
+}
+
+pub fn decode(code: &str) -> Result<Challenge, GameError> {
+    let bytes = from_base64(code.trim()).ok_or(GameError::InvalidChallengeCode)?;
+
+    let (payload, check) = match bytes.split_last() {
+        Some((check, payload)) => (payload, *check),
+        _ => return Err(GameError::InvalidChallengeCode),
+    };
+
+    if checksum(payload) != check {
+        return Err(GameError::InvalidChallengeCode);
+    }
+    let (difficulty, length, max_guesses, high, low) = match *payload {
+        [VERSION, difficulty, length, guesses, high, low] if guesses > 0 => {
+            (difficulty, length, guesses as usize, high, low)
+        }
+        [VERSION_WITHOUT_GUESSES, difficulty, length, high, low] => {
+            (difficulty, length, DEFAULT_GUESSES, high, low)
+        }
+        _ => return Err(GameError::InvalidChallengeCode),
+    };
+
+    let difficulty = match difficulty {
+        0 => GameDifficulty::Easy,
+        1 => GameDifficulty::Hard,
+        2 => GameDifficulty::Ultra,
+        3 => GameDifficulty::Guided,
+        _ => return Err(GameError::InvalidChallengeCode),
+    };
+
+    let index = u16::from_be_bytes([high, low]) as usize;
+    let answer = dictionary_list()
+        .get(index)
+        .filter(|w| w.chars().count() == length as usize)
+        .ok_or(GameError::InvalidChallengeCode)?
+        .clone();
+
+    Ok(Challenge {
+        answer,
+        difficulty,
+        max_guesses,
+    })
+}
+
+pub fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0x5a, |acc: u8, b| acc.rotate_left(3) ^ b)
+}
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::new();
+
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+
+        // every 3 bytes become 4 characters; a short last chunk needs fewer characters
+        for i in 0..=chunk.len() {
+            let sextet = (n >> (18 - 6 * i)) & 0b11_1111;
+            out.push(ALPHABET[sextet as usize] as char);
+        }
+    }
+
+    out
+}
+
+pub fn from_base64(text: &str) -> Option<Vec<u8>> {
+    let sextets = text
+        .bytes()
+        .map(|c| ALPHABET.iter().position(|a| *a == c).map(|p| p as u32))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut out = vec![];
+
+    for chunk in sextets.chunks(4) {
+        if chunk.len() < 2 {
+            return None;
+        }
+
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (i, s)| acc | (s << (18 - 6 * i)));
+        let [_, a, b, c] = n.to_be_bytes();
+        out.extend_from_slice(&[a, b, c][..chunk.len() - 1]);
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a_challenge_survives_a_round_trip() {
+        for difficulty in [GameDifficulty::Hard, GameDifficulty::Guided] {
+            let code = encode("slump", difficulty, 6).unwrap();
+            assert_eq!(
+                decode(&code),
+                Ok(Challenge {
+                    answer: "slump".to_string(),
+                    difficulty,
+                    max_guesses: 6,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_a_challenge_keeps_the_number_of_guesses() {
+        let code = encode("slump", GameDifficulty::Easy, 4).unwrap();
+        assert_eq!(decode(&code).unwrap().max_guesses, 4);
+        // one byte is all the code has room for
+        assert_eq!(encode("slump", GameDifficulty::Easy, 256), None);
+    }
+
+    #[test]
+    fn test_a_code_from_before_the_guesses_were_stored_gets_six() {
+        let index = dictionary_list().iter().position(|w| w == "slump").unwrap();
+        let [high, low] = (index as u16).to_be_bytes();
+        let mut bytes = vec![VERSION_WITHOUT_GUESSES, 1, 5, high, low];
+        bytes.push(checksum(&bytes));
+
+        assert_eq!(
+            decode(&to_base64(&bytes)),
+            Ok(Challenge {
+                answer: "slump".to_string(),
+                difficulty: GameDifficulty::Hard,
+                max_guesses: 6,
+            })
+        );
+    }
+
+    #[test]
+    fn test_the_code_does_not_contain_the_answer() {
+        let code = encode("slump", GameDifficulty::Easy, 6).unwrap();
+        assert!(!code.to_lowercase().contains("slump"));
+    }
+
+    #[test]
+    fn test_words_outside_the_word_list_cannot_be_encoded() {
+        assert_eq!(encode("djkle", GameDifficulty::Easy, 6), None);
+    }
+
+    #[test]
+    fn test_a_tampered_code_is_rejected() {
+        let code = encode("slump", GameDifficulty::Easy, 6).unwrap();
+        let mut tampered: Vec<char> = code.chars().collect();
+        tampered[2] = if tampered[2] == 'A' { 'B' } else { 'A' };
+        let tampered: String = tampered.into_iter().collect();
+
+        assert_eq!(decode(&tampered), Err(GameError::InvalidChallengeCode));
+    }
+
+    #[test]
+    fn test_garbage_is_rejected() {
+        assert_eq!(decode(""), Err(GameError::InvalidChallengeCode));
+        assert_eq!(decode("not a code!"), Err(GameError::InvalidChallengeCode));
+        assert_eq!(decode("AAAA"), Err(GameError::InvalidChallengeCode));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::engine::{GuessResult, HitAccuracy, WordGuess};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Everything the feedback so far tells us about the answer. We use ordered collections so that
+// when a guess breaks more than one rule, we always complain about the same one first.
+#[derive(Debug, Default, PartialEq)]
+pub struct Constraints {
+    // letters we know are in a given position, keyed by index
+    pub greens: BTreeMap<usize, char>,
+    // the fewest copies of a letter the answer can have
+    pub min_counts: BTreeMap<char, usize>,
+    // letters that are not in the answer at all
+    pub excluded: BTreeSet<char>,
+}
+
+impl Constraints {
+    pub fn from_guesses(guesses: &[WordGuess]) -> Self {
+        let mut constraints = Constraints::default();
+
+        for guess in guesses {
+            let mut found_in_guess: BTreeMap<char, usize> = BTreeMap::new();
+
+            for (index, gl) in guess.letters().iter().enumerate() {
+                if gl.accuracy == HitAccuracy::InRightPlace {
+                    constraints.greens.insert(index, gl.letter);
+                }
+                if gl.accuracy != HitAccuracy::NotInWord {
+                    *found_in_guess.entry(gl.letter).or_insert(0) += 1;
+                }
+            }
+
+            // a gray letter only rules the letter out if no other copy of it was found
+            for gl in guess.letters() {
+                if gl.accuracy == HitAccuracy::NotInWord && !found_in_guess.contains_key(&gl.letter)
+                {
+                    constraints.excluded.insert(gl.letter);
+                }
+            }
+
+            for (letter, count) in found_in_guess {
+                let min = constraints.min_counts.entry(letter).or_insert(0);
+                *min = (*min).max(count);
+            }
+        }
+
+        constraints
+    }
+
+    // The hard mode rules: every green has to stay where it is, and every letter we've found has
+    // to be played again, as many times as we know it appears.
+    pub fn hard_mode_violation(&self, word: &str) -> Option<GuessResult> {
+        for (index, letter) in self.greens.iter() {
+            if word.chars().nth(*index) != Some(*letter) {
+                // we start counting at 1, so we can say "the first letter"
+                return Some(GuessResult::LetterDoesNotMatch(*letter, index + 1));
+            }
+        }
+
+        for (letter, min) in self.min_counts.iter() {
+            if word.chars().filter(|c| c == letter).count() < *min {
+                return Some(GuessResult::DoesNotIncludeRequiredLetter(*letter));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GuessLetter;
+
+    #[rustfmt::skip]
+    fn word_guess(word: &str, accuracies: &[HitAccuracy]) -> WordGuess {
+        WordGuess {
+            letters: word.chars().zip(accuracies).map(|(letter, accuracy)| GuessLetter { letter, accuracy: *accuracy }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_constraints_are_collected_from_every_guess() {
+        use HitAccuracy::*;
+        // the answer is 'sleep'
+        let guesses = vec![
+            word_guess(
+                "spell",
+                &[InRightPlace, InWord, InRightPlace, InWord, NotInWord],
+            ),
+            word_guess(
+                "sheet",
+                &[
+                    InRightPlace,
+                    NotInWord,
+                    InRightPlace,
+                    InRightPlace,
+                    NotInWord,
+                ],
+            ),
+        ];
+
+        let constraints = Constraints::from_guesses(&guesses);
+
+        assert_eq!(
+            constraints.greens,
+            BTreeMap::from([(0, 's'), (2, 'e'), (3, 'e')])
+        );
+        assert_eq!(
+            constraints.min_counts,
+            BTreeMap::from([('e', 2), ('l', 1), ('p', 1), ('s', 1)])
+        );
+        assert_eq!(constraints.excluded, BTreeSet::from(['h', 't']));
+    }
+
+    #[test]
+    fn test_a_gray_copy_of_a_found_letter_does_not_exclude_it() {
+        use HitAccuracy::*;
+        // the answer is 'ahead'; only one of the three d's is in the word
+        let guesses = vec![word_guess(
+            "added",
+            &[InRightPlace, NotInWord, NotInWord, InWord, InRightPlace],
+        )];
+
+        let constraints = Constraints::from_guesses(&guesses);
+        assert!(!constraints.excluded.contains(&'d'));
+        assert_eq!(constraints.min_counts.get(&'d'), Some(&1));
+    }
+
+    #[test]
+    fn test_hard_mode_violations() {
+        use HitAccuracy::*;
+        let guesses = vec![word_guess(
+            "sleep",
+            &[NotInWord, NotInWord, NotInWord, InRightPlace, NotInWord],
+        )];
+        let constraints = Constraints::from_guesses(&guesses);
+
+        assert_eq!(
+            constraints.hard_mode_violation("hours"),
+            Some(GuessResult::LetterDoesNotMatch('e', 4))
+        );
+        assert_eq!(constraints.hard_mode_violation("abbey"), None);
+    }
+}
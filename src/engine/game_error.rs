@@ -1,4 +1,82 @@
+use crate::engine::GuessResult;
+use std::fmt;
+use std::path::PathBuf;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum GameError {
+    // the game was lost, but it was played with the answer kept hidden
+    AnswerHidden,
     GameNotLostError,
+    InvalidAnswer(String),
+    InvalidBoardCount(usize),
+    InvalidChallengeCode,
+    InvalidReplayCode,
+    InvalidShareGrid,
+    InvalidSnapshot,
+    InvalidGuessCount(usize),
+    // a starting guess, and why it couldn't be played
+    InvalidInitialGuess(String, GuessResult),
+    // how long the pattern was, and how long the words are
+    InvalidPattern(usize, usize),
+    InvalidWordLength(usize),
+    NoWordsOfLength(usize),
+    // the path, and why it couldn't be read
+    UnreadableDictionary(PathBuf, String),
 }
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::AnswerHidden => write!(f, "the answer is hidden for this game"),
+            GameError::GameNotLostError => write!(f, "the game has not been lost"),
+            GameError::InvalidAnswer(answer) => {
+                write!(f, "'{answer}' is not a word in the dictionary")
+            }
+            GameError::InvalidBoardCount(count) => {
+                write!(f, "{count} is not a valid number of boards")
+            }
+            GameError::InvalidChallengeCode => write!(f, "that challenge code is not valid"),
+            GameError::InvalidReplayCode => write!(f, "that replay code is not valid"),
+            GameError::InvalidShareGrid => write!(f, "that share grid could not be read"),
+            GameError::InvalidSnapshot => write!(f, "the saved game could not be restored"),
+            GameError::InvalidGuessCount(count) => {
+                write!(f, "{count} is not a valid number of guesses for this game")
+            }
+            GameError::InvalidInitialGuess(guess, result) => {
+                let reason = match result {
+                    GuessResult::IncorrectCharacterCount => "it's the wrong length",
+                    GuessResult::NotInDictionary => "it isn't in the dictionary",
+                    GuessResult::DuplicateGuess => "it's already one of them",
+                    GuessResult::GameIsAlreadyOver => "it ends the game before it starts",
+                    _ => "it isn't allowed",
+                };
+                write!(f, "'{guess}' can't be a starting guess: {reason}")
+            }
+            GameError::InvalidPattern(length, word_length) => {
+                write!(
+                    f,
+                    "the pattern has {length} letters, but the words have {word_length}. \
+                     Use --word-length to look for longer or shorter words"
+                )
+            }
+            GameError::InvalidWordLength(length) => {
+                write!(f, "{length} is not a valid word length for this game")
+            }
+            GameError::NoWordsOfLength(length) => {
+                write!(
+                    f,
+                    "the dictionary has no words that are {length} letters long"
+                )
+            }
+            GameError::UnreadableDictionary(path, reason) => {
+                write!(
+                    f,
+                    "could not read the dictionary {}: {reason}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
@@ -1,9 +1,31 @@
-use crate::engine::game_error::GameError;
-
+pub use crate::engine::game_error::GameError;
+
+pub use crate::engine::constraints::Constraints;
+pub use crate::engine::session::Session;
+pub use crate::engine::share::parse_share_grid;
+pub use crate::engine::snapshot::GameSnapshot;
+pub use crate::engine::solver::{consistent_words, suggest};
+pub use crate::engine::stats::{GameResult, Stats};
+pub use crate::engine::utils::json_string;
+
+use chrono::NaiveDate;
+use clap::ArgEnum;
+use rand::RngCore;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use word_source::{WordLists, WordSource};
 
+mod challenge;
+mod constraints;
 mod game_error;
+mod replay;
+mod session;
+mod share;
+mod snapshot;
+mod solver;
+mod stats;
 mod utils;
+mod word_source;
 mod words;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -13,10 +35,22 @@ pub enum GameStatus {
     Lost,
 }
 
+impl GameStatus {
+    // how --batch and saved games write it
+    pub fn name(self) -> &'static str {
+        match self {
+            GameStatus::Won => "won",
+            GameStatus::InProgress => "in_progress",
+            GameStatus::Lost => "lost",
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum GuessResult {
     DoesNotIncludeRequiredLetter(char),
     LetterDoesNotMatch(char, usize),
+    UsesEliminatedLetter(char),
     DuplicateGuess,
     GameIsAlreadyOver,
     IncorrectCharacterCount,
@@ -24,28 +58,71 @@ pub enum GuessResult {
     Valid,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+// The order of the variants matters: the derived ordering puts the best result first, so
+// InRightPlace < InWord < NotInWord. Use is_better_than rather than comparing them directly
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd)]
 pub enum HitAccuracy {
     InRightPlace,
     InWord,
     NotInWord,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+impl HitAccuracy {
+    // whether this tells the player more about the letter than other does. The keyboard shows
+    // the best a letter has done, so a green letter stays green after a guess where it's gray
+    pub fn is_better_than(self, other: HitAccuracy) -> bool {
+        self < other
+    }
+}
+
+// --difficulty and the config file take these by name, in lower case
+#[derive(ArgEnum, Copy, Clone, Debug, PartialEq)]
 pub enum GameDifficulty {
     Easy,
     Hard,
+    // hard mode, and letters that are known not to be in the answer can't be played again
+    Ultra,
+    // hard mode's rules as advice: a guess that breaks them is still played, with a warning
+    Guided,
+    // there's no answer until there has to be one. Every guess gets whichever colors leave the
+    // most words still possible, like Absurdle
+    Absurd,
+}
+
+impl GameDifficulty {
+    // the name saved games store it under too
+    pub fn name(self) -> &'static str {
+        self.to_possible_value()
+            .expect("every difficulty has a name")
+            .get_name()
+    }
 }
 
 pub struct Game {
     guesses: Vec<WordGuess>,
     answer: String,
     difficulty: GameDifficulty,
+    word_length: usize,
+    max_guesses: usize,
     game_status: GameStatus,
     correct_positions: HashSet<usize>,
-    dictionary: HashSet<String>,
+    hinted_positions: HashSet<usize>,
+    hints_used: usize,
+    dictionary: Box<dyn WordSource>,
+    skip_dictionary_check: bool,
+    allow_duplicates: bool,
+    reveal_on_loss: bool,
+    answers_from_dictionary: bool,
+    dictionary_path: Option<PathBuf>,
+    // in guided mode, the hard mode rule the last guess broke
+    hard_mode_warning: Option<GuessResult>,
+    // how many of the guesses were already on the board when the game started
+    initial_guesses: usize,
     played_letters: HashMap<char, HitAccuracy>,
     row_states: Vec<RowState>,
+    // in absurd mode, every word that fits the colors given so far. The answer is just one of
+    // them until only one is left
+    candidates: Option<Vec<String>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -55,16 +132,16 @@ pub struct WordGuess {
 
 impl WordGuess {
     pub fn word(&self) -> String {
-        self.letters
-            .as_slice()
-            .into_iter()
-            .map(|gl| gl.letter)
-            .collect()
+        self.letters.as_slice().iter().map(|gl| gl.letter).collect()
     }
 
     pub fn letters(&self) -> &[GuessLetter] {
         self.letters.as_slice()
     }
+
+    pub fn accuracies(&self) -> Vec<HitAccuracy> {
+        self.letters.iter().map(|gl| gl.accuracy).collect()
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -73,6 +150,18 @@ pub struct GuessLetter {
     pub accuracy: HitAccuracy,
 }
 
+// how `guess` is colored when `answer` is the answer. This is the only place the colors are
+// worked out, and it doesn't touch any game, so it's safe to call for words that aren't played
+pub fn evaluate(answer: &str, guess: &str) -> WordGuess {
+    WordGuess {
+        letters: guess
+            .chars()
+            .zip(solver::feedback(guess, answer))
+            .map(|(letter, accuracy)| GuessLetter { letter, accuracy })
+            .collect(),
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RowState {
     Empty,
@@ -80,9 +169,48 @@ pub enum RowState {
     AlreadyGuessed,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct GameSummary {
+    pub status: GameStatus,
+    pub guesses_used: usize,
+    pub in_right_place: usize,
+    pub in_word: usize,
+    pub not_in_word: usize,
+}
+
+#[derive(Clone)]
 pub struct GameOptions {
     pub answer: Option<String>,
     pub difficulty: GameDifficulty,
+    pub word_length: usize,
+    pub max_guesses: usize,
+    // for practice only: any combination of letters is accepted as a guess
+    pub skip_dictionary_check: bool,
+    // a word that has already been played can be played again, and uses up a guess
+    pub allow_duplicates: bool,
+    // Set to false to keep the answer secret after a loss, so the next player can still try the
+    // same puzzle
+    pub reveal_on_loss: bool,
+    // pick the answer from the whole dictionary rather than the shorter list of answers
+    pub answers_from_dictionary: bool,
+    // picks the same answer every time, for sharing a puzzle or for testing
+    pub seed: Option<u64>,
+    // plays the puzzle for this day instead of a random one
+    pub daily: Option<NaiveDate>,
+    // the player's own word list, used instead of the built in one. The answer comes from it too
+    pub dictionary_path: Option<PathBuf>,
+    // keeps the words in a sorted list rather than a HashSet, for very long word lists
+    pub sorted_dictionary: bool,
+    // The words validate loaded, so that however many games are made from these options, a
+    // player's own list is only read once. Game::new doesn't read files, so without these it
+    // plays with the built-in words, and a dictionary_path left unloaded is an error
+    pub words: Option<WordLists>,
+    // answers from earlier games, oldest first. A random answer isn't one of them, unless every
+    // word has been played
+    pub played_answers: Vec<String>,
+    // guesses that are already on the board when the game starts, to set up a puzzle. They're
+    // the puzzle's rather than the player's, so hard mode only applies to the guesses after them
+    pub initial_guesses: Vec<String>,
 }
 
 impl Default for GameOptions {
@@ -90,31 +218,227 @@ impl Default for GameOptions {
         GameOptions {
             answer: None,
             difficulty: GameDifficulty::Easy,
+            word_length: 5,
+            max_guesses: 6,
+            skip_dictionary_check: false,
+            allow_duplicates: false,
+            reveal_on_loss: true,
+            answers_from_dictionary: false,
+            seed: None,
+            daily: None,
+            dictionary_path: None,
+            sorted_dictionary: false,
+            words: None,
+            played_answers: vec![],
+            initial_guesses: vec![],
+        }
+    }
+}
+
+impl GameOptions {
+    pub fn from_challenge_code(code: &str) -> Result<Self, GameError> {
+        let challenge = challenge::decode(code)?;
+        Ok(GameOptions {
+            word_length: challenge.answer.chars().count(),
+            answer: Some(challenge.answer),
+            difficulty: challenge.difficulty,
+            max_guesses: challenge.max_guesses,
+            ..Default::default()
+        })
+    }
+
+    // Game::new trusts its options, so anything that comes from the player should be checked here
+    // first. Otherwise we could end up with an empty board, or no answer to pick. The words it
+    // checked against are handed back, to be played with.
+    pub fn validate(&self) -> Result<WordLists, GameError> {
+        self.check_board_size()?;
+
+        let words = match (&self.words, &self.dictionary_path) {
+            (Some(words), _) => words.clone(),
+            (None, Some(path)) => WordLists::load(path, self.word_length, self.sorted_dictionary)?,
+            (None, None) => WordLists::built_in(self.sorted_dictionary),
+        };
+
+        // absurd mode ignores the answer it's given, but it still needs words to pick from
+        if self.difficulty == GameDifficulty::Absurd
+            && !utils::has_words_of_length(words.answers(), self.word_length)
+        {
+            return Err(GameError::NoWordsOfLength(self.word_length));
         }
+
+        match &self.answer {
+            Some(answer) if !words.contains(answer) => {
+                Err(GameError::InvalidAnswer(answer.clone()))
+            }
+            Some(answer) if answer.chars().count() != self.word_length => {
+                Err(GameError::InvalidWordLength(self.word_length))
+            }
+            None if !utils::has_words_of_length(words.answers(), self.word_length) => {
+                Err(GameError::NoWordsOfLength(self.word_length))
+            }
+            _ => self.validate_initial_guesses(&words),
+        }?;
+        Ok(words)
+    }
+
+    // a board with no rows or no columns has nowhere to put a guess. This is the one check a game
+    // makes for itself, since it's cheap and the game can't be drawn without it
+    fn check_board_size(&self) -> Result<(), GameError> {
+        if self.max_guesses == 0 {
+            return Err(GameError::InvalidGuessCount(self.max_guesses));
+        }
+
+        if self.word_length == 0 {
+            return Err(GameError::InvalidWordLength(self.word_length));
+        }
+
+        Ok(())
+    }
+
+    // Plays the starting guesses on a game of their own. Hard mode doesn't apply to them, and a
+    // random answer is never one of them, so whether they can be played doesn't depend on the
+    // answer. They still have to leave the player something to guess
+    fn validate_initial_guesses(&self, words: &WordLists) -> Result<(), GameError> {
+        if self.initial_guesses.is_empty() {
+            return Ok(());
+        }
+
+        let mut game = Game::new(GameOptions {
+            initial_guesses: vec![],
+            played_answers: self.initial_guesses.clone(),
+            words: Some(words.clone()),
+            ..self.clone()
+        })?;
+        game.difficulty = match game.difficulty {
+            GameDifficulty::Absurd => GameDifficulty::Absurd,
+            _ => GameDifficulty::Easy,
+        };
+
+        for guess in &self.initial_guesses {
+            let result = match game.guess(guess) {
+                (GameStatus::InProgress, result) => result,
+                (_, GuessResult::Valid) => GuessResult::GameIsAlreadyOver,
+                (_, result) => result,
+            };
+            if result != GuessResult::Valid {
+                return Err(GameError::InvalidInitialGuess(guess.clone(), result));
+            }
+        }
+        Ok(())
     }
 }
 
 impl Game {
-    pub fn new(args: GameOptions) -> Self {
-        Game {
-            guesses: Vec::with_capacity(6),
-            answer: args
-                .answer
-                .map_or_else(|| utils::get_random_word(), |a| a.to_string()),
+    pub fn new(args: GameOptions) -> Result<Self, GameError> {
+        let mut words = match (&args.words, &args.dictionary_path) {
+            (Some(words), _) => words.clone(),
+            (None, None) => WordLists::built_in(args.sorted_dictionary),
+            // playing the built-in words instead would look like the player's list had loaded
+            (None, Some(path)) => {
+                return Err(GameError::UnreadableDictionary(
+                    path.clone(),
+                    String::from("it was never loaded"),
+                ))
+            }
+        };
+        if args.answers_from_dictionary {
+            words.answers = words.dictionary.clone();
+        }
+        // the game only says it has a player's own words when it really does
+        let args = GameOptions {
+            dictionary_path: words.path.clone(),
+            ..args
+        };
+
+        let mut rng = utils::seeded_rng(args.seed);
+        Game::with_words(args, Box::new(words), &mut rng)
+    }
+
+    // For options read back from a saved game or a code someone shared, which could say anything.
+    // Game::new trusts its options, so these are validated first
+    fn from_untrusted(options: GameOptions) -> Result<Self, GameError> {
+        let words = options.validate()?;
+        Game::new(GameOptions {
+            words: Some(words),
+            ..options
+        })
+    }
+
+    // Plays with these words rather than the ones the options would load, and picks a random
+    // answer with `rng` rather than the options' seed. Like Game::new, it trusts the options, so a
+    // forced answer doesn't have to be one of the words
+    pub fn with_words(
+        args: GameOptions,
+        words: Box<dyn WordSource>,
+        rng: &mut dyn RngCore,
+    ) -> Result<Self, GameError> {
+        args.check_board_size()?;
+
+        let candidates = (args.difficulty == GameDifficulty::Absurd).then(|| {
+            utils::sorted_words_of_length(words.answers(), args.word_length)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>()
+        });
+        let answer = match (&candidates, args.answer) {
+            (Some(candidates), _) => candidates[0].clone(),
+            (None, Some(answer)) => answer,
+            (None, None) => match args.daily {
+                Some(day) => utils::get_daily_word(words.answers(), args.word_length, day),
+                None => {
+                    // so the starting guesses don't solve the puzzle before it starts
+                    let played: Vec<String> = args
+                        .played_answers
+                        .iter()
+                        .chain(&args.initial_guesses)
+                        .cloned()
+                        .collect();
+                    words.random_answer(args.word_length, rng, &played)
+                }
+            },
+        };
+
+        let mut game = Game {
+            guesses: Vec::with_capacity(args.max_guesses),
+            answer,
             difficulty: args.difficulty,
+            word_length: args.word_length,
+            max_guesses: args.max_guesses,
             game_status: GameStatus::InProgress,
             correct_positions: HashSet::new(),
-            dictionary: utils::dictionary(),
+            hinted_positions: HashSet::new(),
+            hints_used: 0,
+            dictionary: words,
+            skip_dictionary_check: args.skip_dictionary_check,
+            allow_duplicates: args.allow_duplicates,
+            reveal_on_loss: args.reveal_on_loss,
+            answers_from_dictionary: args.answers_from_dictionary,
+            dictionary_path: args.dictionary_path,
+            hard_mode_warning: None,
+            initial_guesses: 0,
             played_letters: HashMap::new(),
-            row_states: vec![
-                RowState::Current,
-                RowState::Empty,
-                RowState::Empty,
-                RowState::Empty,
-                RowState::Empty,
-                RowState::Empty,
-            ],
+            row_states: std::iter::once(RowState::Current)
+                .chain(std::iter::repeat_n(
+                    RowState::Empty,
+                    args.max_guesses.saturating_sub(1),
+                ))
+                .collect(),
+            candidates,
+        };
+
+        // validate has already checked they can all be played. Absurd mode has no rules to lift,
+        // and still has to dodge them
+        let difficulty = game.difficulty;
+        if difficulty != GameDifficulty::Absurd {
+            game.difficulty = GameDifficulty::Easy;
+        }
+        for guess in &args.initial_guesses {
+            if game.guess(guess).1 == GuessResult::Valid {
+                game.initial_guesses += 1;
+            }
         }
+        game.difficulty = difficulty;
+        Ok(game)
     }
 
     pub fn game_status(&self) -> GameStatus {
@@ -122,36 +446,273 @@ impl Game {
     }
 
     pub fn get_answer(&self) -> Result<String, GameError> {
-        if self.game_status == GameStatus::Lost {
-            Ok(self.answer.to_string())
-        } else {
-            Err(GameError::GameNotLostError)
+        match (self.game_status, self.reveal_on_loss) {
+            (GameStatus::Lost, true) => Ok(self.answer.to_string()),
+            (GameStatus::Lost, false) => Err(GameError::AnswerHidden),
+            _ => Err(GameError::GameNotLostError),
         }
     }
 
-    pub fn guesses(&self) -> &[WordGuess] {
-        self.guesses.as_slice()
+    // The colors `word` would get, without playing it. Nothing about the game changes, and the
+    // word isn't checked against the dictionary or the rules. In absurd mode the answer isn't
+    // settled yet, so this only says what the first word still in the running would give
+    pub fn preview(&self, word: &str) -> WordGuess {
+        evaluate(&self.answer, word)
     }
 
-    fn in_dictionary(&self, word: &str) -> bool {
-        self.dictionary.get(word).is_some()
+    // a strong first guess for this game's dictionary
+    pub fn opener(&self) -> String {
+        solver::best_opener(self.dictionary.as_ref())
     }
 
-    fn answer_char_at_index(&self, index: usize) -> char {
-        self.answer.chars().nth(index).unwrap()
+    // For --cheats only: the answer whatever the state of the game. In absurd mode there isn't
+    // one until it's been narrowed down to a single word
+    pub fn peek_answer(&self) -> Option<&str> {
+        match self.candidates {
+            Some(_) => None,
+            None => Some(&self.answer),
+        }
+    }
+
+    // Gives away the answer's letter at the first position the player hasn't found yet, and
+    // doesn't give the same one away twice
+    pub fn hint(&mut self) -> Option<(usize, char)> {
+        // in absurd mode the answer can still change, so there's nothing to give away yet
+        if self.game_status != GameStatus::InProgress || self.candidates.is_some() {
+            return None;
+        }
+
+        let (index, letter) = self.answer.chars().enumerate().find(|(index, _)| {
+            !self.correct_positions.contains(index) && !self.hinted_positions.contains(index)
+        })?;
+
+        self.hinted_positions.insert(index);
+        self.hints_used += 1;
+        Some((index, letter))
     }
 
-    fn matches_answer_at_index(&self, index: usize, letter: char) -> bool {
-        letter == self.answer_char_at_index(index)
+    // every character the words are made of, in order. That's a to z for the built-in words
+    pub fn alphabet(&self) -> Vec<char> {
+        let mut alphabet: Vec<char> = self.dictionary.words().flat_map(|w| w.chars()).collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+        alphabet
     }
 
-    fn recalculate_row_states(&mut self) -> () {
-        let number_of_guesses_so_far = self.guesses().len();
+    // The words that fit a pattern like "s..te", where a dot is any letter, and have every one
+    // of `letters` somewhere in them, as often as it's given. In order, for --find
+    pub fn find_words(&self, pattern: &str, letters: &str) -> Result<Vec<String>, GameError> {
+        let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+        if pattern.len() != self.word_length {
+            return Err(GameError::InvalidPattern(pattern.len(), self.word_length));
+        }
+        let required = utils::build_letter_counts(&letters.to_lowercase());
+
+        let fits = |word: &String| {
+            let counts = utils::build_letter_counts(word);
+            word.chars().count() == pattern.len()
+                && word
+                    .chars()
+                    .zip(&pattern)
+                    .all(|(letter, wanted)| *wanted == '.' || letter == *wanted)
+                && required
+                    .iter()
+                    .all(|(letter, count)| counts.get(letter).unwrap_or(&0) >= count)
+        };
+        let mut found: Vec<String> = self
+            .dictionary
+            .words()
+            .filter(|w| fits(w))
+            .cloned()
+            .collect();
+        found.sort_unstable();
+        Ok(found)
+    }
+
+    // the answer's letters the player has found or been given by a hint, by position
+    pub fn known_letters(&self) -> Vec<Option<char>> {
+        self.answer
+            .chars()
+            .enumerate()
+            .map(|(index, letter)| {
+                (self.correct_positions.contains(&index) || self.hinted_positions.contains(&index))
+                    .then_some(letter)
+            })
+            .collect()
+    }
+
+    // we only rate the answer once the game is over, so the rating can't be used as a clue
+    pub fn answer_difficulty(&self) -> Option<u8> {
+        match self.game_status {
+            GameStatus::InProgress => None,
+            _ => Some(utils::estimate_difficulty(&self.answer)),
+        }
+    }
+
+    // a short meaning for the answer, when there is one. Like the rating, it would give the
+    // answer away, so it waits for the game to end
+    pub fn answer_definition(&self) -> Option<String> {
+        // a hidden answer's meaning would give it away
+        match (self.game_status, self.reveal_on_loss) {
+            (GameStatus::InProgress, _) | (GameStatus::Lost, false) => None,
+            _ => utils::definition(&self.answer),
+        }
+    }
+
+    // totals up the feedback from every guess, once the game is over
+    pub fn summary(&self) -> Option<GameSummary> {
+        if self.game_status == GameStatus::InProgress {
+            return None;
+        }
+
+        let mut summary = GameSummary {
+            status: self.game_status,
+            guesses_used: self.guesses_used(),
+            in_right_place: 0,
+            in_word: 0,
+            not_in_word: 0,
+        };
+
+        for gl in self.guesses.iter().flat_map(|g| g.letters()) {
+            match gl.accuracy {
+                HitAccuracy::InRightPlace => summary.in_right_place += 1,
+                HitAccuracy::InWord => summary.in_word += 1,
+                HitAccuracy::NotInWord => summary.not_in_word += 1,
+            }
+        }
+
+        Some(summary)
+    }
+
+    // what the stats need to know about this game, once it is over
+    pub fn result(&self) -> Option<GameResult> {
+        match self.game_status {
+            GameStatus::InProgress => None,
+            status => Some(GameResult {
+                won: status == GameStatus::Won,
+                guesses_used: self.guesses_used(),
+                hints_used: self.hints_used,
+                solve_time: None,
+            }),
+        }
+    }
+
+    // A plain text copy of the board for snapshot tests, one guess per line, e.g. "heart GYY.Y"
+    // where G is in the right place, Y is in the word, and . is not in the word.
+    #[cfg(test)]
+    pub fn to_fixture_string(&self) -> String {
+        let status = match self.game_status {
+            GameStatus::Won => "won",
+            GameStatus::InProgress => "in progress",
+            GameStatus::Lost => "lost",
+        };
+
+        let rows = self.guesses.iter().map(|guess| {
+            let accuracies: String = guess
+                .letters()
+                .iter()
+                .map(|gl| match gl.accuracy {
+                    HitAccuracy::InRightPlace => 'G',
+                    HitAccuracy::InWord => 'Y',
+                    HitAccuracy::NotInWord => '.',
+                })
+                .collect();
+            format!("{} {}", guess.word(), accuracies)
+        });
+
+        std::iter::once(format!("answer {}", self.answer))
+            .chain(std::iter::once(format!("status {status}")))
+            .chain(rows)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // how many words could still be the answer in absurd mode
+    pub fn candidates_left(&self) -> Option<usize> {
+        self.candidates.as_ref().map(|candidates| candidates.len())
+    }
+
+    // Splits the words that are still possible by the colors this guess would give them, and
+    // keeps the biggest group. On a tie we give away as little as we can, so greens lose to
+    // yellows and yellows lose to grays. Any word left gives the same colors, so the first one
+    // stands in as the answer and evaluate colors the guess as usual.
+    fn dodge_guess(&mut self, guess_input: &str) {
+        let candidates = match self.candidates.take() {
+            Some(candidates) => candidates,
+            None => return,
+        };
+
+        let mut groups: HashMap<Vec<HitAccuracy>, Vec<String>> = HashMap::new();
+        for word in candidates {
+            groups
+                .entry(solver::feedback(guess_input, &word))
+                .or_default()
+                .push(word);
+        }
 
-        let row_states = vec![1, 2, 3, 4, 5, 6]
+        let given_away = |pattern: &[HitAccuracy]| {
+            pattern
+                .iter()
+                .map(|accuracy| match accuracy {
+                    HitAccuracy::InRightPlace => 2,
+                    HitAccuracy::InWord => 1,
+                    HitAccuracy::NotInWord => 0,
+                })
+                .sum::<usize>()
+        };
+        let (_, kept) = groups
             .into_iter()
+            .max_by_key(|(pattern, words)| {
+                (
+                    words.len(),
+                    std::cmp::Reverse(given_away(pattern)),
+                    std::cmp::Reverse(words[0].clone()),
+                )
+            })
+            .expect("the answer is always one of the candidates");
+
+        self.answer = kept[0].clone();
+        self.candidates = Some(kept);
+    }
+
+    // Lets a friend play the same puzzle without seeing the answer. The code only has room for
+    // the answer, the difficulty and the number of guesses, so a game played with other words or
+    // other rules doesn't get one: the friend would be playing a different game
+    pub fn challenge_code(&self) -> Option<String> {
+        if self.dictionary_path.is_some() || self.skip_dictionary_check || self.allow_duplicates {
+            return None;
+        }
+        challenge::encode(&self.answer, self.difficulty, self.max_guesses)
+    }
+
+    // lets a friend watch a finished game guess by guess, with the same colors
+    pub fn to_replay(&self) -> Option<String> {
+        replay::encode(self)
+    }
+
+    pub fn from_replay(code: &str) -> Result<Game, GameError> {
+        replay::decode(code)
+    }
+
+    pub fn guesses(&self) -> &[WordGuess] {
+        self.guesses.as_slice()
+    }
+
+    // the row the last valid guess added, so its colors can be read straight after guessing
+    pub fn last_guess(&self) -> Option<&WordGuess> {
+        self.guesses.last()
+    }
+
+    fn in_dictionary(&self, word: &str) -> bool {
+        self.skip_dictionary_check || self.dictionary.contains(word)
+    }
+
+    fn recalculate_row_states(&mut self) {
+        let number_of_guesses_so_far = self.guesses().len();
+
+        let row_states = (1..=self.max_guesses)
             .map(|i| {
-                if number_of_guesses_so_far == 6 {
+                if number_of_guesses_so_far == self.max_guesses {
                     return RowState::AlreadyGuessed;
                 }
 
@@ -160,7 +721,8 @@ impl Game {
                 }
 
                 if i == number_of_guesses_so_far + 1 {
-                    if self.game_status == GameStatus::Won {
+                    // a game that was given up has no row left to type into either
+                    if self.game_status != GameStatus::InProgress {
                         return RowState::Empty;
                     }
                     return RowState::Current;
@@ -171,17 +733,25 @@ impl Game {
             .collect();
 
         self.row_states = row_states;
-        ()
     }
 
-    fn recalculate_played_letter_registry(&mut self, guess: &WordGuess) -> () {
+    // the positions the player has found, which hints skip over
+    fn record_correct_positions(&mut self, guess: &WordGuess) {
+        for (index, gl) in guess.letters().iter().enumerate() {
+            if gl.accuracy == HitAccuracy::InRightPlace {
+                self.correct_positions.insert(index);
+            }
+        }
+    }
+
+    fn recalculate_played_letter_registry(&mut self, guess: &WordGuess) {
         for gl in guess.letters() {
             match self.played_letters.get_mut(&gl.letter) {
                 None => {
                     self.played_letters.insert(gl.letter, gl.accuracy);
                 }
                 Some(accuracy_value) => {
-                    if gl.accuracy < *accuracy_value {
+                    if gl.accuracy.is_better_than(*accuracy_value) {
                         *accuracy_value = gl.accuracy;
                     }
                 }
@@ -194,52 +764,89 @@ impl Game {
         existing_guesses.contains(&guess_input.to_string())
     }
 
-    pub fn guess(&mut self, guess_input: &str) -> (GameStatus, GuessResult) {
-        if self.game_status == GameStatus::Won || self.game_status == GameStatus::Lost {
-            return (self.game_status, GuessResult::GameIsAlreadyOver);
+    pub fn constraints(&self) -> Constraints {
+        Constraints::from_guesses(&self.guesses)
+    }
+
+    // In hard mode every guess has to fit the clues so far. The answer always fits, but if it
+    // isn't in the dictionary there may be no word left that the player is allowed to play.
+    pub fn is_stuck(&self) -> bool {
+        if matches!(
+            self.difficulty,
+            GameDifficulty::Easy | GameDifficulty::Guided | GameDifficulty::Absurd
+        ) || self.game_status != GameStatus::InProgress
+            || self.skip_dictionary_check
+        {
+            return false;
         }
 
-        if guess_input.len() != 5 {
-            return (self.game_status, GuessResult::IncorrectCharacterCount);
+        let constraints = self.constraints();
+        !self.dictionary.words().any(|word| {
+            (self.allow_duplicates || !self.guess_already_exists(word))
+                && self.difficulty_violation(&constraints, word).is_none()
+        })
+    }
+
+    // the rules a guess has to follow on top of being a word, which depend on the difficulty
+    fn difficulty_violation(&self, constraints: &Constraints, word: &str) -> Option<GuessResult> {
+        if matches!(
+            self.difficulty,
+            GameDifficulty::Easy | GameDifficulty::Guided | GameDifficulty::Absurd
+        ) {
+            return None;
         }
 
-        if self.guess_already_exists(&guess_input) {
-            return (self.game_status, GuessResult::DuplicateGuess);
+        if let Some(violation) = constraints.hard_mode_violation(word) {
+            return Some(violation);
         }
 
-        if !self.in_dictionary(&guess_input) {
-            return (self.game_status, GuessResult::NotInDictionary);
+        if self.difficulty == GameDifficulty::Ultra {
+            // a letter's accuracy is the best one it has had, so a gray letter was never found
+            return word
+                .chars()
+                .find(|c| self.played_letters.get(c) == Some(&HitAccuracy::NotInWord))
+                .map(GuessResult::UsesEliminatedLetter);
         }
 
-        if self.difficulty == GameDifficulty::Hard {
-            for (index, letter) in guess_input.chars().enumerate() {
-                if self.correct_positions.contains(&index) {
-                    if !self.matches_answer_at_index(index, letter) {
-                        let char_at_index = self.answer_char_at_index(index);
-                        return (
-                            self.game_status,
-                            // we start counting at 1, so we can say "the first letter"
-                            GuessResult::LetterDoesNotMatch(char_at_index, index + 1),
-                        );
-                    }
-                }
-            }
+        None
+    }
 
-            for letter in self.answer.chars() {
-                let is_discovered = self.is_letter_uncovered(letter);
+    // why a guess can't be played, without playing it
+    fn rejection(&self, guess_input: &str) -> Option<GuessResult> {
+        if self.game_status == GameStatus::Won || self.game_status == GameStatus::Lost {
+            return Some(GuessResult::GameIsAlreadyOver);
+        }
 
-                if is_discovered {
-                    if !guess_input.contains(letter) {
-                        return (
-                            self.game_status,
-                            GuessResult::DoesNotIncludeRequiredLetter(letter),
-                        );
-                    }
-                }
-            }
+        // count characters rather than bytes, so accented letters only count once
+        if guess_input.chars().count() != self.word_length {
+            return Some(GuessResult::IncorrectCharacterCount);
+        }
+
+        if !self.allow_duplicates && self.guess_already_exists(guess_input) {
+            return Some(GuessResult::DuplicateGuess);
+        }
+
+        if !self.in_dictionary(guess_input) {
+            return Some(GuessResult::NotInDictionary);
+        }
+
+        self.difficulty_violation(&self.constraints(), guess_input)
+    }
+
+    pub fn guess(&mut self, guess_input: &str) -> (GameStatus, GuessResult) {
+        if let Some(rejection) = self.rejection(guess_input) {
+            return (self.game_status, rejection);
         }
 
-        let guess = self.build_guess(&guess_input);
+        self.hard_mode_warning = match self.difficulty {
+            GameDifficulty::Guided => self.constraints().hard_mode_violation(guess_input),
+            _ => None,
+        };
+
+        self.dodge_guess(guess_input);
+        // the colors are worked out without touching the game, and only then recorded
+        let guess = self.preview(guess_input);
+        self.record_correct_positions(&guess);
         self.recalculate_played_letter_registry(&guess);
 
         self.guesses.push(guess);
@@ -256,87 +863,52 @@ impl Game {
             return (self.game_status, GuessResult::Valid);
         }
 
-        if self.guesses.len() == 6 {
+        if self.guesses_remaining() == 0 {
             self.game_status = GameStatus::Lost;
         }
 
         (self.game_status, GuessResult::Valid)
     }
 
-    pub fn row_states(&self) -> Vec<RowState> {
-        self.row_states.clone()
-    }
-
-    pub fn is_letter_uncovered(&self, letter: char) -> bool {
-        match &self.get_letter_match_state(letter) {
-            None => false,
-            Some(HitAccuracy::NotInWord) => false,
-            Some(_) => true,
+    // Ends the game as a loss, with the guesses left unused, so the answer can be shown
+    pub fn give_up(&mut self) {
+        if self.game_status != GameStatus::InProgress {
+            return;
         }
+
+        self.game_status = GameStatus::Lost;
+        self.recalculate_row_states();
     }
 
-    fn build_guess(&mut self, guess_input: &str) -> WordGuess {
-        let mut discoverable_letters = utils::build_letter_counts(&self.answer);
-        let mut guess_letters: Vec<Option<GuessLetter>> = vec![None, None, None, None, None];
+    // lost before running out of guesses
+    pub fn was_given_up(&self) -> bool {
+        self.game_status == GameStatus::Lost && self.guesses_remaining() > 0
+    }
 
-        // Weird stuff. We walk the word twice; We go over the correct guesses first, so that we
-        // can subtract their letters from the count of available letters to colorize.
-        for (idx, c) in guess_input.chars().enumerate() {
-            if self.matches_answer_at_index(idx, c) {
-                guess_letters[idx] =
-                    Some(self.build_guess_letter_with_accuracy(idx, c, &mut discoverable_letters))
-            }
-        }
+    // the hard mode rule the last guess broke, which only guided mode lets a guess do
+    pub fn hard_mode_warning(&self) -> Option<GuessResult> {
+        self.hard_mode_warning
+    }
 
-        // Then we go over the letters that are not correct.
-        for (idx, c) in guess_input.chars().enumerate() {
-            if guess_letters[idx].is_none() {
-                guess_letters[idx] =
-                    Some(self.build_guess_letter_with_accuracy(idx, c, &mut discoverable_letters))
-            }
-        }
+    pub fn row_states(&self) -> Vec<RowState> {
+        self.row_states.clone()
+    }
 
-        WordGuess {
-            letters: guess_letters.iter().map(|o| o.unwrap()).collect(),
-        }
+    pub fn word_length(&self) -> usize {
+        self.word_length
     }
 
-    fn build_guess_letter_with_accuracy(
-        &mut self,
-        letter_index: usize,
-        raw_letter: char,
-        discoverable_letters: &mut HashMap<char, usize>,
-    ) -> GuessLetter {
-        let accuracy = match &self.answer.contains(raw_letter) {
-            true => {
-                let in_same_place = self.matches_answer_at_index(letter_index, raw_letter);
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
 
-                if in_same_place {
-                    if let Some(ch) = discoverable_letters.get_mut(&raw_letter) {
-                        *ch -= 1;
-                    }
-                    self.correct_positions.insert(letter_index);
-                    HitAccuracy::InRightPlace
-                } else {
-                    if let Some(ch) = discoverable_letters.get_mut(&raw_letter) {
-                        if (*ch) >= 1 {
-                            *ch -= 1;
-                            HitAccuracy::InWord
-                        } else {
-                            HitAccuracy::NotInWord
-                        }
-                    } else {
-                        HitAccuracy::NotInWord
-                    }
-                }
-            }
-            false => HitAccuracy::NotInWord,
-        };
+    pub fn guesses_used(&self) -> usize {
+        self.guesses.len()
+    }
 
-        GuessLetter {
-            letter: raw_letter,
-            accuracy: accuracy,
-        }
+    // never less than zero, even for a restored game with more guesses than it allows
+    pub fn guesses_remaining(&self) -> usize {
+        self.max_guesses.saturating_sub(self.guesses.len())
     }
 
     pub fn get_letter_match_state(&self, letter: char) -> Option<HitAccuracy> {
@@ -347,13 +919,16 @@ impl Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     fn test_add_guess() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("pasta");
         assert_eq!(game.guesses.len(), 1)
     }
@@ -361,7 +936,7 @@ mod tests {
     #[rustfmt::skip]
     #[test]
     fn test_a_guess_is_stored_correctly() {
-        let mut game = Game::new(GameOptions { answer: Some("haste".to_string()), difficulty: GameDifficulty::Easy});
+        let mut game = Game::new(GameOptions { answer: Some("haste".to_string()), difficulty: GameDifficulty::Easy, ..Default::default()}).unwrap();
         game.guess("heart");
 
         let spell_guess = super::WordGuess {
@@ -376,101 +951,993 @@ mod tests {
         assert_eq!(game.guesses[0], spell_guess)
     }
 
-    #[rustfmt::skip]
     #[test]
-    fn test_letters_are_marked_in_word_until_the_count_of_letters_is_met() {
-        let mut game = Game::new(GameOptions { answer: Some("sleep".to_string()), difficulty: GameDifficulty::Easy});
-        game.guess("spell");
-        // we guess spell. Only one of the l's should match as InWord, because there is only one l in sleep
-        // Similarly, only one of the e's should match
+    fn test_the_last_guess_is_the_row_that_was_just_added() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("haste".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.last_guess(), None);
 
-        let spell_guess = super::WordGuess {
-            letters: vec![
-                GuessLetter { letter: 's', accuracy: HitAccuracy::InRightPlace },
-                GuessLetter { letter: 'p', accuracy: HitAccuracy::InWord },
-                GuessLetter { letter: 'e', accuracy: HitAccuracy::InRightPlace },
-                GuessLetter { letter: 'l', accuracy: HitAccuracy::InWord },
-                GuessLetter { letter: 'l', accuracy: HitAccuracy::NotInWord }
-            ],
-        };
-        assert_eq!(game.guesses[0], spell_guess)
+        game.guess("heart");
+        game.guess("zzzzz");
+        let last = game.last_guess().unwrap();
+        assert_eq!(last.word(), "heart");
+        assert_eq!(Some(last), game.guesses().last());
+
+        game.guess("haste");
+        assert_eq!(
+            game.last_guess().unwrap().accuracies(),
+            vec![HitAccuracy::InRightPlace; 5]
+        );
+        assert_eq!(game.last_guess(), game.guesses().get(1));
     }
 
-    #[rustfmt::skip]
     #[test]
-    fn test_counts_apply_to_the_in_right_place_characters_first() {
-        let mut game = Game::new(GameOptions { answer: Some("ahead".to_string()), difficulty: GameDifficulty::Easy});
-        game.guess("added");
-        // The guess 'added' has 3 'd' characters, but the answer only has one.
-        // The 'd' char in the correct place (the last char) should be marked as in the right place,
-        // and the other chars should be marked as NotInWord
+    fn test_a_preview_is_the_guess_without_playing_it() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("alien".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let preview = game.preview("sleep");
+        assert!(game.guesses().is_empty());
+        assert_eq!(game.get_letter_match_state('s'), None);
+        assert_eq!(game.game_status(), GameStatus::InProgress);
+        assert_eq!(preview, evaluate("alien", "sleep"));
+
+        game.guess("sleep");
+        assert_eq!(game.guesses(), [preview]);
+
+        // even the winning word doesn't win
+        let win = game.preview("alien");
+        assert!(win
+            .accuracies()
+            .iter()
+            .all(|a| *a == HitAccuracy::InRightPlace));
+        assert_eq!(game.game_status(), GameStatus::InProgress);
+        assert_eq!(game.guesses_used(), 1);
+    }
+
+    #[test]
+    fn test_correct_positions_are_only_recorded_for_played_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            ..Default::default()
+        })
+        .unwrap();
+        game.preview("slept");
+        game.preview("slump");
+        assert!(game.correct_positions.is_empty());
+        assert!(game.played_letters.is_empty());
+
+        game.guess("plumb");
+        assert_eq!(game.correct_positions, HashSet::from([1, 2, 3]));
+
+        // turned down, so it doesn't count even though s and p would be green
+        assert_eq!(
+            game.guess("stump").1,
+            GuessResult::LetterDoesNotMatch('l', 2)
+        );
+        game.preview("slump");
+        assert_eq!(game.correct_positions, HashSet::from([1, 2, 3]));
+
+        // and hints still start from the first letter that wasn't found
+        assert_eq!(game.hint(), Some((0, 's')));
+    }
+
+    #[test]
+    fn test_evaluating_a_guess_longer_than_the_answer_does_not_panic() {
+        use HitAccuracy::*;
+        assert_eq!(
+            evaluate("cat", "catch").accuracies(),
+            vec![
+                InRightPlace,
+                InRightPlace,
+                InRightPlace,
+                NotInWord,
+                NotInWord
+            ]
+        );
+    }
+
+    #[test]
+    fn test_the_answer_can_be_peeked_at_during_a_game() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.peek_answer(), Some("slump"));
+        game.guess("slump");
+        assert_eq!(game.peek_answer(), Some("slump"));
+
+        let absurd = Game::new(GameOptions {
+            difficulty: GameDifficulty::Absurd,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(absurd.peek_answer(), None);
+    }
+
+    #[test]
+    fn test_guesses_are_counted_as_they_are_used() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 3,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!((game.guesses_used(), game.guesses_remaining()), (0, 3));
+
+        game.guess("slept");
+        // a word that's turned down doesn't use up a guess
+        game.guess("zzzzz");
+        assert_eq!((game.guesses_used(), game.guesses_remaining()), (1, 2));
+
+        game.guess("plumb");
+        game.guess("bless");
+        assert_eq!(game.game_status(), GameStatus::Lost);
+        assert_eq!((game.guesses_used(), game.guesses_remaining()), (3, 0));
+        game.guess("slump");
+        assert_eq!((game.guesses_used(), game.guesses_remaining()), (3, 0));
+    }
+
+    #[test]
+    fn test_a_won_game_keeps_the_guesses_it_did_not_need() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("plumb");
+        game.guess("slump");
+        assert_eq!(game.game_status(), GameStatus::Won);
+        assert_eq!((game.guesses_used(), game.guesses_remaining()), (2, 4));
+    }
+
+    #[test]
+    fn test_accuracies_are_ranked_from_right_place_to_not_in_word() {
+        use HitAccuracy::*;
+        assert!(InRightPlace < InWord);
+        assert!(InWord < NotInWord);
+
+        assert!(InRightPlace.is_better_than(InWord));
+        assert!(InRightPlace.is_better_than(NotInWord));
+        assert!(InWord.is_better_than(NotInWord));
+        assert!(!NotInWord.is_better_than(InWord));
+        assert!(!InWord.is_better_than(InRightPlace));
+        for accuracy in [InRightPlace, InWord, NotInWord] {
+            assert!(!accuracy.is_better_than(accuracy));
+        }
+    }
+
+    #[test]
+    fn test_the_keyboard_keeps_the_best_accuracy_for_a_letter() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        // s stays green after a guess where the extra s is gray, and p goes from yellow to green
+        game.guess("slept");
+        assert_eq!(
+            game.get_letter_match_state('s'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        game.guess("bless");
+        assert_eq!(
+            game.get_letter_match_state('s'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        assert_eq!(
+            game.get_letter_match_state('l'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        assert_eq!(
+            game.get_letter_match_state('b'),
+            Some(HitAccuracy::NotInWord)
+        );
+
+        game.guess("plumb");
+        assert_eq!(game.get_letter_match_state('p'), Some(HitAccuracy::InWord));
+        game.guess("plump");
+        assert_eq!(
+            game.get_letter_match_state('p'),
+            Some(HitAccuracy::InRightPlace)
+        );
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn test_letters_are_marked_in_word_until_the_count_of_letters_is_met() {
+        let mut game = Game::new(GameOptions { answer: Some("sleep".to_string()), difficulty: GameDifficulty::Easy, ..Default::default()}).unwrap();
+        game.guess("spell");
+        // we guess spell. Only one of the l's should match as InWord, because there is only one l in sleep
+        // Similarly, only one of the e's should match
+
+        let spell_guess = super::WordGuess {
+            letters: vec![
+                GuessLetter { letter: 's', accuracy: HitAccuracy::InRightPlace },
+                GuessLetter { letter: 'p', accuracy: HitAccuracy::InWord },
+                GuessLetter { letter: 'e', accuracy: HitAccuracy::InRightPlace },
+                GuessLetter { letter: 'l', accuracy: HitAccuracy::InWord },
+                GuessLetter { letter: 'l', accuracy: HitAccuracy::NotInWord }
+            ],
+        };
+        assert_eq!(game.guesses[0], spell_guess)
+    }
+
+    #[rustfmt::skip]
+    #[test]
+    fn test_counts_apply_to_the_in_right_place_characters_first() {
+        let mut game = Game::new(GameOptions { answer: Some("ahead".to_string()), difficulty: GameDifficulty::Easy, ..Default::default()}).unwrap();
+        game.guess("added");
+        // The guess 'added' has 3 'd' characters, but the answer only has one.
+        // The 'd' char in the correct place (the last char) should be marked as in the right place,
+        // and the other chars should be marked as NotInWord
+
+        let spell_guess = super::WordGuess {
+            letters: vec![
+                GuessLetter { letter: 'a', accuracy: HitAccuracy::InRightPlace },
+                GuessLetter { letter: 'd', accuracy: HitAccuracy::NotInWord },
+                GuessLetter { letter: 'd', accuracy: HitAccuracy::NotInWord },
+                GuessLetter { letter: 'e', accuracy: HitAccuracy::InWord },
+                GuessLetter { letter: 'd', accuracy: HitAccuracy::InRightPlace }
+            ],
+        };
+        assert_eq!(game.guesses[0], spell_guess)
+    }
+
+    #[test]
+    fn test_cannot_add_duplicate_guess() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("pasta");
+        let (_, duplicate_guess) = game.guess("pasta");
+        assert_eq!(duplicate_guess, GuessResult::DuplicateGuess);
+    }
+
+    #[test]
+    fn test_a_duplicate_guess_uses_up_a_guess_when_allowed() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            allow_duplicates: true,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("pasta");
+        let (_, duplicate_guess) = game.guess("pasta");
+        assert_eq!(duplicate_guess, GuessResult::Valid);
+        assert_eq!(game.guesses_used(), 2);
+    }
+
+    #[test]
+    fn test_a_correct_guess_wins_the_game() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let (won_the_game, _) = game.guess("slump");
+        assert_eq!(won_the_game, GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_guess_cannot_be_less_than_five_characters() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let (_, char_count_wrong) = game.guess("slp");
+        assert_eq!(char_count_wrong, GuessResult::IncorrectCharacterCount);
+    }
+
+    #[test]
+    fn test_a_guess_cannot_be_more_than_five_characters() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let (_, char_count_wrong) = game.guess("slumffffp");
+        assert_eq!(char_count_wrong, GuessResult::IncorrectCharacterCount);
+    }
+
+    #[test]
+    fn test_multi_byte_characters_are_counted_once() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            skip_dictionary_check: true,
+            ..Default::default()
+        })
+        .unwrap();
+        // five characters, but six bytes
+        let (_, five_characters) = game.guess("crème");
+        assert_eq!(five_characters, GuessResult::Valid);
+
+        // four characters, but five bytes
+        let (_, four_characters) = game.guess("café");
+        assert_eq!(four_characters, GuessResult::IncorrectCharacterCount);
+
+        let (_, six_characters) = game.guess("cafés!");
+        assert_eq!(six_characters, GuessResult::IncorrectCharacterCount);
+    }
+
+    #[test]
+    fn test_multi_byte_characters_are_scored() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("crème".to_string()),
+            skip_dictionary_check: true,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("ème..");
+        assert_eq!(
+            game.guesses()[0].accuracies(),
+            vec![
+                HitAccuracy::InWord,
+                HitAccuracy::InWord,
+                HitAccuracy::InWord,
+                HitAccuracy::NotInWord,
+                HitAccuracy::NotInWord
+            ]
+        );
+
+        let (status, _) = game.guess("crème");
+        assert_eq!(status, GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_short_answer_does_not_panic() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("cat".to_string()),
+            skip_dictionary_check: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let (status, result) = game.guess("catch");
+        assert_eq!(status, GameStatus::InProgress);
+        assert_eq!(result, GuessResult::Valid);
+    }
+
+    fn words(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    // a word list that isn't a HashSet, to show the game only goes through WordSource
+    struct ThreeWords([String; 3]);
+
+    impl WordSource for ThreeWords {
+        fn contains(&self, word: &str) -> bool {
+            self.0.iter().any(|w| w == word)
+        }
+
+        fn words(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+            Box::new(self.0.iter())
+        }
+    }
+
+    #[test]
+    fn test_a_game_can_be_played_with_its_own_word_source() {
+        let source = || Box::new(ThreeWords(["crane", "slump", "plumb"].map(String::from)));
+        let mut game = Game::with_words(
+            GameOptions::default(),
+            source(),
+            &mut utils::seeded_rng(Some(7)),
+        )
+        .unwrap();
+        let answer = game.answer.clone();
+        assert!(["crane", "slump", "plumb"].contains(&answer.as_str()));
+
+        assert_eq!(game.guess("about").1, GuessResult::NotInDictionary);
+        let other = if answer == "crane" { "slump" } else { "crane" };
+        assert_eq!(game.guess(other).1, GuessResult::Valid);
+        assert_eq!(game.guess(&answer), (GameStatus::Won, GuessResult::Valid));
+
+        let absurd = Game::with_words(
+            GameOptions {
+                difficulty: GameDifficulty::Absurd,
+                ..Default::default()
+            },
+            source(),
+            &mut utils::seeded_rng(None),
+        )
+        .unwrap();
+        assert_eq!(absurd.candidates_left(), Some(3));
+    }
+
+    #[test]
+    fn test_the_same_random_numbers_and_words_pick_the_same_answer() {
+        let answer = |seed: u64| {
+            let words = ThreeWords(["crane", "slump", "plumb"].map(String::from));
+            let mut rng = StdRng::seed_from_u64(seed);
+            Game::with_words(GameOptions::default(), Box::new(words), &mut rng)
+                .unwrap()
+                .answer
+        };
+        for seed in 0..20 {
+            assert_eq!(answer(seed), answer(seed));
+        }
+        // and the seeds don't all land on one word
+        let picked: HashSet<String> = (0..20).map(answer).collect();
+        assert!(picked.len() > 1);
+    }
+
+    #[test]
+    fn test_a_six_letter_game_can_be_won() {
+        let mut game = Game::with_words(
+            GameOptions {
+                answer: Some("planet".to_string()),
+                word_length: 6,
+                ..Default::default()
+            },
+            Box::new(words(&["planet", "plants"])),
+            &mut utils::seeded_rng(None),
+        )
+        .unwrap();
+
+        let (_, valid_word) = game.guess("plants");
+        assert_eq!(valid_word, GuessResult::Valid);
+        assert_eq!(game.guesses()[0].letters().len(), 6);
+
+        let (won_the_game, _) = game.guess("planet");
+        assert_eq!(won_the_game, GameStatus::Won);
+    }
+
+    #[test]
+    fn test_a_six_letter_game_rejects_five_letter_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("planet".to_string()),
+            word_length: 6,
+            ..Default::default()
+        })
+        .unwrap();
+        let (_, char_count_wrong) = game.guess("plant");
+        assert_eq!(char_count_wrong, GuessResult::IncorrectCharacterCount);
+    }
+
+    #[test]
+    fn test_a_game_with_zero_guesses_or_letters_is_not_made() {
+        for (options, error) in [
+            (
+                GameOptions {
+                    max_guesses: 0,
+                    ..Default::default()
+                },
+                GameError::InvalidGuessCount(0),
+            ),
+            (
+                GameOptions {
+                    word_length: 0,
+                    ..Default::default()
+                },
+                GameError::InvalidWordLength(0),
+            ),
+        ] {
+            // even without validate
+            assert_eq!(Game::new(options.clone()).err(), Some(error.clone()));
+            let mut rng = StdRng::seed_from_u64(1);
+            let game = Game::with_words(options, Box::new(words(&["slump"])), &mut rng);
+            assert_eq!(game.err(), Some(error));
+        }
+    }
+
+    #[test]
+    fn test_options_with_a_zero_word_length_are_rejected() {
+        let options = GameOptions {
+            word_length: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate().err(),
+            Some(GameError::InvalidWordLength(0))
+        );
+    }
+
+    #[test]
+    fn test_options_with_no_words_of_the_right_length_are_rejected() {
+        let options = GameOptions {
+            word_length: 9,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate().err(),
+            Some(GameError::NoWordsOfLength(9))
+        );
+        assert!(GameOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_options_with_an_answer_of_the_wrong_length_are_rejected() {
+        let options = GameOptions {
+            answer: Some("slump".to_string()),
+            word_length: 6,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate().err(),
+            Some(GameError::InvalidWordLength(6))
+        );
+    }
+
+    #[test]
+    fn test_the_game_is_lost_after_six_incorrect_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("admit");
+        game.guess("adorn");
+        game.guess("adult");
+        game.guess("affix");
+        game.guess("afire");
+        let (lost_the_game, _) = game.guess("after");
+        assert_eq!(lost_the_game, GameStatus::Lost);
+    }
+
+    #[test]
+    fn test_a_four_guess_game_is_lost_after_four_incorrect_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 4,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.row_states().len(), 4);
+
+        game.guess("admit");
+        game.guess("adorn");
+        game.guess("adult");
+        let (lost_the_game, _) = game.guess("affix");
+        assert_eq!(lost_the_game, GameStatus::Lost);
+        assert_eq!(game.row_states(), vec![RowState::AlreadyGuessed; 4]);
+    }
+
+    #[test]
+    fn test_a_ten_guess_game_keeps_going_after_six_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 10,
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            game.guess(word);
+        }
+        assert_eq!(game.game_status(), GameStatus::InProgress);
+        assert_eq!(game.row_states()[6], RowState::Current);
+        assert_eq!(game.row_states().len(), 10);
+
+        for word in ["agony", "alarm", "album"] {
+            game.guess(word);
+        }
+        let (lost_the_game, _) = game.guess("alert");
+        assert_eq!(lost_the_game, GameStatus::Lost);
+    }
+
+    #[test]
+    fn test_random_answers_come_from_the_answer_list() {
+        let answers = utils::answers();
+        for _ in 0..50 {
+            let game = Game::new(GameOptions::default()).unwrap();
+            assert!(answers.contains(&game.answer));
+        }
+    }
+
+    #[test]
+    fn test_random_answers_can_come_from_the_whole_dictionary() {
+        let answers = utils::answers();
+        let outside_the_answer_list = (0..50)
+            .map(|_| {
+                Game::new(GameOptions {
+                    answers_from_dictionary: true,
+                    ..Default::default()
+                })
+                .unwrap()
+            })
+            .any(|game| !answers.contains(&game.answer));
+        assert!(outside_the_answer_list);
+    }
+
+    #[test]
+    fn test_words_outside_the_answer_list_can_still_be_guessed() {
+        assert!(!utils::answers().contains("abase"));
+
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let (_, result) = game.guess("abase");
+        assert_eq!(result, GuessResult::Valid);
+    }
+
+    #[test]
+    fn test_games_with_the_same_seed_have_the_same_answer() {
+        let seeded = || {
+            Game::new(GameOptions {
+                seed: Some(2022),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        assert_eq!(seeded().answer, seeded().answer);
+    }
+
+    #[test]
+    fn test_the_daily_puzzle_ignores_the_seed() {
+        let daily = |seed| {
+            Game::new(GameOptions {
+                daily: Some(NaiveDate::from_ymd(2022, 1, 28)),
+                seed: Some(seed),
+                ..Default::default()
+            })
+            .unwrap()
+        };
+        assert_eq!(daily(1).answer, daily(2).answer);
+    }
+
+    #[test]
+    fn test_ultra_mode_rejects_letters_known_not_to_be_in_the_word() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Ultra,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("admit");
+
+        // still has to follow the hard mode rules first
+        let (_, result) = game.guess("shelf");
+        assert_eq!(result, GuessResult::DoesNotIncludeRequiredLetter('m'));
+
+        let (_, result) = game.guess("mango");
+        assert_eq!(result, GuessResult::UsesEliminatedLetter('a'));
+        assert_eq!(game.guesses().len(), 1);
+    }
+
+    #[test]
+    fn test_ultra_mode_allows_letters_that_were_yellow() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Ultra,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("admit");
+
+        let (_, result) = game.guess("mucus");
+        assert_eq!(result, GuessResult::Valid);
+    }
+
+    fn absurd_game() -> Game {
+        Game::new(GameOptions {
+            difficulty: GameDifficulty::Absurd,
+            max_guesses: 30,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_absurd_mode_never_has_more_words_left_after_a_guess() {
+        let mut game = absurd_game();
+        let mut left = game.candidates_left().unwrap();
+        assert_eq!(
+            left,
+            utils::sorted_words_of_length(utils::answers().iter(), 5).len()
+        );
+
+        // always guessing a word that could still be the answer means the game has to end
+        while game.game_status() == GameStatus::InProgress {
+            let word = game.candidates.as_ref().unwrap()[0].clone();
+            assert_eq!(game.guess(&word).1, GuessResult::Valid);
+
+            let now = game.candidates_left().unwrap();
+            assert!(now <= left);
+            left = now;
+        }
+
+        assert_eq!(game.game_status(), GameStatus::Won);
+        assert_eq!(game.candidates_left(), Some(1));
+        assert!(game.guesses().len() > 1);
+    }
+
+    #[test]
+    fn test_absurd_mode_only_pins_the_answer_when_one_word_is_left() {
+        let mut game = absurd_game();
+        // slump could be the answer, but too many other words are still possible
+        let (status, _) = game.guess("slump");
+        assert_eq!(status, GameStatus::InProgress);
+        assert!(game.candidates_left().unwrap() > 1);
+        assert_ne!(game.guesses()[0].word(), game.answer);
+
+        // every word that's left would have colored slump the same way
+        let colors = game.guesses()[0].accuracies();
+        for word in game.candidates.as_ref().unwrap() {
+            assert_eq!(solver::feedback("slump", word), colors);
+        }
+    }
+
+    #[test]
+    fn test_giving_up_loses_the_game_and_shows_the_answer() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("crane");
+        assert_eq!(game.get_answer(), Err(GameError::GameNotLostError));
+
+        game.give_up();
+        assert_eq!(game.game_status(), GameStatus::Lost);
+        assert_eq!(game.get_answer(), Ok("slump".to_string()));
+        assert!(game.was_given_up());
+        assert_eq!(game.guesses_used(), 1);
+        assert!(!game.row_states().contains(&RowState::Current));
+        assert_eq!(game.guess("slump").1, GuessResult::GameIsAlreadyOver);
+    }
+
+    #[test]
+    fn test_words_are_found_by_pattern_and_letters() {
+        let game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let found = game.find_words("sl.mp", "").unwrap();
+        assert_eq!(found, ["slump"]);
+        let found = game.find_words("S..TE", "").unwrap();
+        assert!(found.contains(&"slate".to_string()) && found.contains(&"suite".to_string()));
+        assert!(found.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // a repeated letter has to be there twice
+        let found = game.find_words(".....", "ee").unwrap();
+        assert!(found.contains(&"sleep".to_string()));
+        assert!(!found.contains(&"slept".to_string()));
+
+        assert_eq!(
+            game.find_words("s..t", ""),
+            Err(GameError::InvalidPattern(4, 5))
+        );
+    }
+
+    #[test]
+    fn test_a_game_can_start_with_guesses_on_the_board() {
+        let options = GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            // hard mode wouldn't let crane follow spilt, but these are the puzzle's guesses
+            initial_guesses: vec!["spilt".to_string(), "crane".to_string()],
+            ..Default::default()
+        };
+        assert!(options.validate().is_ok());
+        let mut game = Game::new(options).unwrap();
+
+        assert_eq!(game.guesses().len(), 2);
+        assert_eq!(game.guesses()[1].word(), "crane");
+        assert_eq!(
+            game.row_states(),
+            [
+                RowState::AlreadyGuessed,
+                RowState::AlreadyGuessed,
+                RowState::Current,
+                RowState::Empty,
+                RowState::Empty,
+                RowState::Empty,
+            ]
+        );
+        assert_eq!(
+            game.get_letter_match_state('s'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        assert_eq!(
+            game.get_letter_match_state('c'),
+            Some(HitAccuracy::NotInWord)
+        );
+
+        // hard mode starts with the player's own guesses
+        assert_eq!(
+            game.guess("chair").1,
+            GuessResult::LetterDoesNotMatch('s', 1)
+        );
+        assert_eq!(game.guess("slump"), (GameStatus::Won, GuessResult::Valid));
+    }
+
+    #[test]
+    fn test_a_random_answer_is_never_a_starting_guess() {
+        let starting: Vec<String> = ["crane", "slate", "plumb"].map(String::from).to_vec();
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..20 {
+            let game = Game::with_words(
+                GameOptions {
+                    initial_guesses: starting.clone(),
+                    max_guesses: 4,
+                    ..Default::default()
+                },
+                Box::new(words(&["crane", "slate", "plumb", "slump"])),
+                &mut rng,
+            )
+            .unwrap();
+            assert_eq!(game.answer, "slump");
+            assert_eq!(game.game_status(), GameStatus::InProgress);
+        }
+    }
 
-        let spell_guess = super::WordGuess {
-            letters: vec![
-                GuessLetter { letter: 'a', accuracy: HitAccuracy::InRightPlace },
-                GuessLetter { letter: 'd', accuracy: HitAccuracy::NotInWord },
-                GuessLetter { letter: 'd', accuracy: HitAccuracy::NotInWord },
-                GuessLetter { letter: 'e', accuracy: HitAccuracy::InWord },
-                GuessLetter { letter: 'd', accuracy: HitAccuracy::InRightPlace }
-            ],
+    #[test]
+    fn test_starting_guesses_have_to_leave_a_guess_to_play() {
+        let options = GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 2,
+            initial_guesses: vec!["crane".to_string(), "spilt".to_string()],
+            ..Default::default()
         };
-        assert_eq!(game.guesses[0], spell_guess)
+        assert_eq!(
+            options.validate().err(),
+            Some(GameError::InvalidInitialGuess(
+                "spilt".to_string(),
+                GuessResult::GameIsAlreadyOver
+            ))
+        );
     }
 
     #[test]
-    fn test_cannot_add_duplicate_guess() {
+    fn test_a_finished_game_cannot_be_given_up() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
-        game.guess("pasta");
-        let (_, duplicate_guess) = game.guess("pasta");
-        assert_eq!(duplicate_guess, GuessResult::DuplicateGuess);
+        })
+        .unwrap();
+        game.guess("slump");
+        game.give_up();
+        assert_eq!(game.game_status(), GameStatus::Won);
+        assert!(!game.was_given_up());
     }
 
     #[test]
-    fn test_a_correct_guess_wins_the_game() {
+    fn test_absurd_mode_has_no_hints_or_challenge_code() {
+        let mut game = absurd_game();
+        assert_eq!(game.hint(), None);
+        assert_eq!(game.challenge_code(), None);
+
+        // the hard mode rules don't apply, so any word can be played next
+        game.guess("admit");
+        assert_eq!(game.guess("fuzzy").1, GuessResult::Valid);
+        assert!(!game.is_stuck());
+    }
+
+    #[test]
+    fn test_hints_reveal_letters_the_player_has_not_found() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
-        let (won_the_game, _) = game.guess("slump");
-        assert_eq!(won_the_game, GameStatus::Won);
+        })
+        .unwrap();
+        // 's' and 'l' are green
+        game.guess("slept");
+
+        assert_eq!(game.hint(), Some((2, 'u')));
+        assert_eq!(game.hint(), Some((3, 'm')));
+        assert_eq!(game.hint(), Some((4, 'p')));
+        assert_eq!(game.hint(), None);
+        assert_eq!(game.hints_used, 3);
+
+        game.guess("slump");
+        assert_eq!(game.result().map(|r| r.hints_used), Some(3));
     }
 
     #[test]
-    fn test_a_guess_cannot_be_less_than_five_characters() {
+    fn test_there_are_no_hints_once_the_game_is_over() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
-        let (_, char_count_wrong) = game.guess("slp");
-        assert_eq!(char_count_wrong, GuessResult::IncorrectCharacterCount);
+        })
+        .unwrap();
+        game.guess("slump");
+        assert_eq!(game.hint(), None);
+        assert_eq!(game.hints_used, 0);
     }
 
     #[test]
-    fn test_a_guess_cannot_be_more_than_five_characters() {
-        let mut game = Game::new(GameOptions {
-            answer: Some("slump".to_string()),
+    fn test_an_answer_has_to_be_in_the_dictionary() {
+        let options = |answer: &str| GameOptions {
+            answer: Some(answer.to_string()),
             ..Default::default()
-        });
-        let (_, char_count_wrong) = game.guess("slumffffp");
-        assert_eq!(char_count_wrong, GuessResult::IncorrectCharacterCount);
+        };
+        assert_eq!(
+            options("djkle").validate().err(),
+            Some(GameError::InvalidAnswer("djkle".to_string()))
+        );
+        assert!(options("slump").validate().is_ok());
     }
 
     #[test]
-    fn test_the_game_is_lost_after_six_incorrect_guesses() {
+    fn test_a_custom_dictionary_replaces_the_built_in_words() {
+        let path = std::env::temp_dir().join(format!("wordlet-custom-{}.txt", std::process::id()));
+        std::fs::write(&path, "Hallo\n welt\nkatze\n").unwrap();
+        let options = |answer: &str| GameOptions {
+            answer: Some(answer.to_string()),
+            dictionary_path: Some(path.clone()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options("slump").validate().err(),
+            Some(GameError::InvalidAnswer("slump".to_string()))
+        );
+        let words = options("katze").validate().unwrap();
+        assert_eq!(words.path, Some(path.clone()));
+        // welt is too short
+        assert_eq!(words.skipped, 1);
+        // the games play with the words validate read, so the file isn't needed any more
+        let _ = std::fs::remove_file(&path);
+
         let mut game = Game::new(GameOptions {
-            answer: Some("slump".to_string()),
+            words: Some(words.clone()),
+            ..options("katze")
+        })
+        .unwrap();
+        assert_eq!(game.guess("slump").1, GuessResult::NotInDictionary);
+        assert_eq!(game.guess("hallo").1, GuessResult::Valid);
+        assert_eq!(game.dictionary_path, Some(path.clone()));
+
+        // with no answer given, it's picked from the custom words
+        let game = Game::new(GameOptions {
+            answer: None,
+            words: Some(words),
+            ..options("katze")
+        })
+        .unwrap();
+        assert!(["hallo", "katze"].contains(&game.answer.as_str()));
+    }
+
+    #[test]
+    fn test_a_dictionary_that_cannot_be_read_is_reported() {
+        let options = GameOptions {
+            dictionary_path: Some(PathBuf::from("/no/such/dir/words.txt")),
             ..Default::default()
-        });
-        game.guess("admit");
-        game.guess("adorn");
-        game.guess("adult");
-        game.guess("affix");
-        game.guess("afire");
-        let (lost_the_game, _) = game.guess("after");
-        assert_eq!(lost_the_game, GameStatus::Lost);
+        };
+        assert!(matches!(
+            options.validate(),
+            Err(GameError::UnreadableDictionary(..))
+        ));
+    }
+
+    #[test]
+    fn test_a_dictionary_is_never_swapped_for_the_built_in_words() {
+        let path = PathBuf::from("/no/such/dir/words.txt");
+        let options = GameOptions {
+            dictionary_path: Some(path.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            Game::new(options).err(),
+            Some(GameError::UnreadableDictionary(
+                path,
+                String::from("it was never loaded")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_options_with_zero_guesses_are_rejected() {
+        let options = GameOptions {
+            max_guesses: 0,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.validate().err(),
+            Some(GameError::InvalidGuessCount(0))
+        );
     }
 
     #[test]
@@ -478,7 +1945,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("slump");
         let (won_the_game, game_already_over) = game.guess("adept");
 
@@ -491,7 +1959,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("admit");
         game.guess("adorn");
         game.guess("adult");
@@ -509,18 +1978,45 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         let (game_continues, invalid_word) = game.guess("djkle");
         assert_eq!(game_continues, GameStatus::InProgress);
         assert_eq!(invalid_word, GuessResult::NotInDictionary);
     }
 
+    #[test]
+    fn test_any_word_is_accepted_and_scored_without_the_dictionary_check() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            skip_dictionary_check: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let (game_continues, valid_word) = game.guess("spmlx");
+        assert_eq!(game_continues, GameStatus::InProgress);
+        assert_eq!(valid_word, GuessResult::Valid);
+
+        let accuracies = game.guesses()[0].accuracies();
+        assert_eq!(
+            accuracies,
+            vec![
+                HitAccuracy::InRightPlace,
+                HitAccuracy::InWord,
+                HitAccuracy::InWord,
+                HitAccuracy::InWord,
+                HitAccuracy::NotInWord
+            ]
+        );
+    }
+
     #[test]
     fn test_can_get_the_answer_after_the_game_is_lost() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("admit");
         game.guess("adorn");
         game.guess("adult");
@@ -530,6 +2026,182 @@ mod tests {
         assert_eq!(game.get_answer(), Err(GameError::GameNotLostError));
         game.guess("aging");
         assert_eq!(game.get_answer(), Ok("slump".to_string()));
+        assert!(game.answer_definition().is_some());
+    }
+
+    #[test]
+    fn test_a_hidden_answer_stays_hidden_after_a_loss() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 1,
+            reveal_on_loss: false,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.get_answer(), Err(GameError::GameNotLostError));
+        game.guess("admit");
+        assert_eq!(game.game_status(), GameStatus::Lost);
+        assert_eq!(game.get_answer(), Err(GameError::AnswerHidden));
+        assert_eq!(game.answer_definition(), None);
+
+        // and a saved game keeps it hidden
+        let restored = Game::restore(&game.snapshot()).unwrap();
+        assert_eq!(restored.get_answer(), Err(GameError::AnswerHidden));
+    }
+
+    #[test]
+    fn test_the_answer_is_only_rated_once_the_game_is_over() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.answer_difficulty(), None);
+
+        game.guess("slump");
+        assert_eq!(
+            game.answer_difficulty(),
+            Some(utils::estimate_difficulty("slump"))
+        );
+    }
+
+    #[test]
+    fn test_fixture_string_for_a_known_game() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("ahead".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("added");
+        game.guess("lease");
+        game.guess("ahead");
+
+        let expected = "\
+answer ahead
+status won
+added G..YG
+lease .YY..
+ahead GGGGG";
+        assert_eq!(game.to_fixture_string(), expected);
+    }
+
+    #[test]
+    fn test_summary_is_only_available_once_the_game_is_over() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("haste".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("heart");
+        assert_eq!(game.summary(), None);
+    }
+
+    #[test]
+    fn test_summary_counts_the_feedback_of_every_guess() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("haste".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        // heart: one green, three yellows, one gray. haste: five greens
+        game.guess("heart");
+        game.guess("haste");
+
+        assert_eq!(
+            game.summary(),
+            Some(GameSummary {
+                status: GameStatus::Won,
+                guesses_used: 2,
+                in_right_place: 6,
+                in_word: 3,
+                not_in_word: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_a_challenge_code_recreates_the_same_puzzle() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            ..Default::default()
+        })
+        .unwrap();
+        let code = game.challenge_code().unwrap();
+
+        let mut challenge = Game::new(GameOptions::from_challenge_code(&code).unwrap()).unwrap();
+        assert_eq!(challenge.difficulty, GameDifficulty::Hard);
+        assert_eq!(challenge.challenge_code(), Some(code));
+
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            game.guess(word);
+            challenge.guess(word);
+        }
+        assert_eq!(challenge.get_answer(), game.get_answer());
+    }
+
+    #[test]
+    fn test_a_challenge_code_keeps_the_number_of_guesses() {
+        let game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 3,
+            ..Default::default()
+        })
+        .unwrap();
+        let code = game.challenge_code().unwrap();
+
+        let challenge = Game::new(GameOptions::from_challenge_code(&code).unwrap()).unwrap();
+        assert_eq!(challenge.max_guesses(), 3);
+        assert_eq!(challenge.challenge_code(), Some(code));
+    }
+
+    #[test]
+    fn test_a_game_with_its_own_words_has_no_challenge_code() {
+        let path = std::env::temp_dir().join(format!("wordlet-challenge-{}", std::process::id()));
+        std::fs::write(&path, "slump\nplumb\n").unwrap();
+        let options = GameOptions {
+            answer: Some("slump".to_string()),
+            dictionary_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let options = GameOptions {
+            words: Some(options.validate().unwrap()),
+            ..options
+        };
+        std::fs::remove_file(&path).unwrap();
+
+        // a friend's game would check guesses against the built-in words instead
+        assert_eq!(Game::new(options).unwrap().challenge_code(), None);
+    }
+
+    #[test]
+    fn test_a_game_without_the_dictionary_check_has_no_challenge_code() {
+        let game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            skip_dictionary_check: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.challenge_code(), None);
+    }
+
+    #[test]
+    fn test_a_game_that_allows_duplicates_has_no_challenge_code() {
+        let game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            allow_duplicates: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.challenge_code(), None);
+    }
+
+    #[test]
+    fn test_an_invalid_challenge_code_is_an_error() {
+        assert_eq!(
+            GameOptions::from_challenge_code("nope").err(),
+            Some(GameError::InvalidChallengeCode)
+        );
     }
 
     #[test]
@@ -537,19 +2209,47 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("abbey".to_string()),
             difficulty: GameDifficulty::Hard,
-        });
+            ..Default::default()
+        })
+        .unwrap();
         game.guess("sleep");
 
         let (_, required_letter) = game.guess("hours");
         assert_eq!(required_letter, GuessResult::LetterDoesNotMatch('e', 4));
     }
 
+    #[test]
+    fn test_guided_mode_plays_a_guess_that_breaks_the_hard_mode_rules() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("abbey".to_string()),
+            difficulty: GameDifficulty::Guided,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("sleep");
+        assert_eq!(game.hard_mode_warning(), None);
+
+        assert_eq!(game.guess("hours").1, GuessResult::Valid);
+        assert_eq!(game.guesses_used(), 2);
+        assert_eq!(
+            game.hard_mode_warning(),
+            Some(GuessResult::LetterDoesNotMatch('e', 4))
+        );
+
+        // the warning is only about the last guess
+        game.guess("cadet");
+        assert_eq!(game.hard_mode_warning(), None);
+        assert!(!game.is_stuck());
+    }
+
     #[test]
     fn test_hard_mode_requires_guessing_letters_that_have_been_found_in_the_word() {
         let mut game = Game::new(GameOptions {
             answer: Some("abbey".to_string()),
             difficulty: GameDifficulty::Hard,
-        });
+            ..Default::default()
+        })
+        .unwrap();
         let (_, valid_word) = game.guess("slept");
         assert_eq!(valid_word, GuessResult::Valid);
 
@@ -560,12 +2260,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hard_mode_requires_repeating_letters_found_more_than_once() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("sleep".to_string()),
+            difficulty: GameDifficulty::Hard,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("eager");
+
+        let (_, required_letter) = game.guess("alien");
+        assert_eq!(
+            required_letter,
+            GuessResult::DoesNotIncludeRequiredLetter('e')
+        );
+    }
+
+    #[test]
+    fn test_constraints_after_several_guesses() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("slept");
+        game.guess("plumb");
+
+        let constraints = game.constraints();
+        assert_eq!(
+            constraints.greens.into_iter().collect::<Vec<_>>(),
+            vec![(0, 's'), (1, 'l'), (2, 'u'), (3, 'm')]
+        );
+        assert_eq!(
+            constraints.min_counts.into_keys().collect::<String>(),
+            "lmpsu"
+        );
+        assert_eq!(constraints.excluded.into_iter().collect::<String>(), "bet");
+    }
+
     #[test]
     fn test_hard_mode_can_include_guesses_with_old_and_new_letters() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             difficulty: GameDifficulty::Hard,
-        });
+            ..Default::default()
+        })
+        .unwrap();
         game.guess("sleep");
 
         let (game_continues, valid_word) = game.guess("sloop");
@@ -573,12 +2314,73 @@ mod tests {
         assert_eq!(valid_word, GuessResult::Valid);
     }
 
+    #[test]
+    fn test_hard_mode_is_not_stuck_while_a_word_fits_the_clues() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("abbey".to_string()),
+            difficulty: GameDifficulty::Hard,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("sleep");
+        assert!(!game.is_stuck());
+    }
+
+    #[test]
+    fn test_hard_mode_is_stuck_when_no_word_fits_the_clues() {
+        let mut game = Game::with_words(
+            GameOptions {
+                answer: Some("abbey".to_string()),
+                difficulty: GameDifficulty::Hard,
+                ..Default::default()
+            },
+            Box::new(words(&["sleep", "hours"])),
+            &mut utils::seeded_rng(None),
+        )
+        .unwrap();
+        assert!(!game.is_stuck());
+
+        game.guess("sleep");
+        assert!(game.is_stuck());
+    }
+
+    #[test]
+    fn test_a_sorted_dictionary_checks_guesses_the_same_way() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            sorted_dictionary: true,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(game.guess("zzzzz").1, GuessResult::NotInDictionary);
+        assert_eq!(game.guess("plump").1, GuessResult::Valid);
+        assert!(!game.is_stuck());
+        assert_eq!(game.guess("slump"), (GameStatus::Won, GuessResult::Valid));
+    }
+
+    #[test]
+    fn test_easy_mode_is_never_stuck() {
+        let mut game = Game::with_words(
+            GameOptions {
+                answer: Some("abbey".to_string()),
+                ..Default::default()
+            },
+            Box::new(words(&["sleep", "hours"])),
+            &mut utils::seeded_rng(None),
+        )
+        .unwrap();
+        game.guess("sleep");
+        assert!(!game.is_stuck());
+    }
+
     #[test]
     fn test_keeps_track_of_which_letters_matched() {
         let mut game = Game::new(GameOptions {
             answer: Some("slump".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("slept");
 
         assert_eq!(
@@ -608,7 +2410,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("laugh".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("larva");
 
         assert_eq!(
@@ -641,7 +2444,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("ahead".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         // we guess 'lease'. The first 'e' should match as InWord, and the second should be NotInWord
         // When we ask for the letter match state, it should respond with InWord
         game.guess("lease");
@@ -660,7 +2464,8 @@ mod tests {
         let game = Game::new(GameOptions {
             answer: Some("laugh".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         assert_eq!(
             game.row_states(),
             vec![
@@ -679,7 +2484,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("laugh".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("admit");
 
         assert_eq!(
@@ -700,7 +2506,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("laugh".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("admit");
         game.guess("laugh");
 
@@ -722,7 +2529,8 @@ mod tests {
         let mut game = Game::new(GameOptions {
             answer: Some("laugh".to_string()),
             ..Default::default()
-        });
+        })
+        .unwrap();
         game.guess("admit");
         game.guess("adorn");
         game.guess("adult");
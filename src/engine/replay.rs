@@ -0,0 +1,305 @@
+use crate::engine::challenge::{checksum, from_base64, to_base64};
+use crate::engine::game_error::GameError;
+use crate::engine::utils::dictionary_list;
+use crate::engine::{Game, GameDifficulty, GameOptions, GameStatus, GuessResult};
+
+// A replay code is a challenge code with the guesses on the end: a version, the difficulty, the
+// word length, the number of guesses allowed, whether repeats were allowed and whether the player
+// gave up, the position of the answer in the word list, the number of guesses played and the
+// position of each, and a checksum. Playing the guesses again gives the same colors, so they
+// don't need to be stored.
+const VERSION: u8 = 1;
+// the bytes before the guesses
+const HEADER_LENGTH: usize = 8;
+const ALLOW_DUPLICATES: u8 = 1;
+const GAVE_UP: u8 = 2;
+
+pub fn encode(game: &Game) -> Option<String> {
+    // A code for a game in progress would give its answer away. The starting guesses of a set
+    // up puzzle weren't checked by hard mode, so they might not play back
+    if game.game_status == GameStatus::InProgress
+        || !game.reveal_on_loss
+        || game.dictionary_path.is_some()
+        || game.initial_guesses > 0
+    {
+        return None;
+    }
+
+    let words = dictionary_list();
+    let index = |word: &str| {
+        let index = words.iter().position(|w| w == word)?;
+        u16::try_from(index).ok()
+    };
+
+    let difficulty = match game.difficulty {
+        GameDifficulty::Easy => 0,
+        GameDifficulty::Hard => 1,
+        GameDifficulty::Ultra => 2,
+        GameDifficulty::Guided => 3,
+        // every guess dodges the same way again, so an absurd game can be replayed too
+        GameDifficulty::Absurd => 4,
+    };
+
+    let mut flags = 0;
+    if game.allow_duplicates {
+        flags |= ALLOW_DUPLICATES;
+    }
+    if game.was_given_up() {
+        flags |= GAVE_UP;
+    }
+
+    let mut bytes = vec![
+        VERSION,
+        difficulty,
+        u8::try_from(game.word_length).ok()?,
+        u8::try_from(game.max_guesses).ok()?,
+        flags,
+    ];
+    bytes.extend_from_slice(&index(&game.answer)?.to_be_bytes());
+    bytes.push(u8::try_from(game.guesses.len()).ok()?);
+    for guess in &game.guesses {
+        bytes.extend_from_slice(&index(&guess.word())?.to_be_bytes());
+    }
+    bytes.push(checksum(&bytes));
+
+    Some(to_base64(&bytes))
+}
+
+pub fn decode(code: &str) -> Result<Game, GameError> {
+    let bytes = from_base64(code.trim()).ok_or(GameError::InvalidReplayCode)?;
+
+    let (payload, check) = match bytes.split_last() {
+        Some((check, payload)) if payload.len() >= HEADER_LENGTH => (payload, *check),
+        _ => return Err(GameError::InvalidReplayCode),
+    };
+
+    let guess_count = payload[HEADER_LENGTH - 1] as usize;
+    if payload[0] != VERSION
+        || checksum(payload) != check
+        || payload.len() != HEADER_LENGTH + 2 * guess_count
+    {
+        return Err(GameError::InvalidReplayCode);
+    }
+
+    let difficulty = match payload[1] {
+        0 => GameDifficulty::Easy,
+        1 => GameDifficulty::Hard,
+        2 => GameDifficulty::Ultra,
+        3 => GameDifficulty::Guided,
+        4 => GameDifficulty::Absurd,
+        _ => return Err(GameError::InvalidReplayCode),
+    };
+    let flags = payload[4];
+    if flags & !(ALLOW_DUPLICATES | GAVE_UP) != 0 {
+        return Err(GameError::InvalidReplayCode);
+    }
+
+    let words = dictionary_list();
+    let word = |bytes: &[u8]| {
+        let index = u16::from_be_bytes([bytes[0], bytes[1]]) as usize;
+        words
+            .get(index)
+            .filter(|w| w.chars().count() == payload[2] as usize)
+            .cloned()
+            .ok_or(GameError::InvalidReplayCode)
+    };
+
+    let options = GameOptions {
+        answer: Some(word(&payload[5..7])?),
+        difficulty,
+        word_length: payload[2] as usize,
+        max_guesses: payload[3] as usize,
+        allow_duplicates: flags & ALLOW_DUPLICATES != 0,
+        ..Default::default()
+    };
+    let mut game = Game::from_untrusted(options).map_err(|_| GameError::InvalidReplayCode)?;
+
+    for guess in payload[HEADER_LENGTH..].chunks(2) {
+        let guess = word(guess)?;
+        if game.game_status != GameStatus::InProgress || game.guess(&guess).1 != GuessResult::Valid
+        {
+            return Err(GameError::InvalidReplayCode);
+        }
+    }
+
+    if flags & GAVE_UP != 0 {
+        game.give_up();
+    }
+
+    // only finished games get a code
+    if game.game_status == GameStatus::InProgress {
+        return Err(GameError::InvalidReplayCode);
+    }
+
+    Ok(game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RowState;
+
+    fn played(options: GameOptions, guesses: &[&str]) -> Game {
+        let mut game = Game::new(options).unwrap();
+        for guess in guesses {
+            assert_eq!(game.guess(guess).1, GuessResult::Valid);
+        }
+        game
+    }
+
+    fn slump(difficulty: GameDifficulty, max_guesses: usize) -> GameOptions {
+        GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty,
+            max_guesses,
+            ..Default::default()
+        }
+    }
+
+    fn assert_same_game(replayed: &Game, game: &Game) {
+        assert_eq!(replayed.guesses(), game.guesses());
+        assert_eq!(replayed.row_states(), game.row_states());
+        assert_eq!(replayed.game_status(), game.game_status());
+        assert_eq!(replayed.get_answer(), game.get_answer());
+        assert_eq!(replayed.difficulty, game.difficulty);
+    }
+
+    #[test]
+    fn test_a_won_game_survives_a_round_trip() {
+        let game = played(slump(GameDifficulty::Hard, 6), &["stare", "shunt", "slump"]);
+        assert_eq!(game.game_status(), GameStatus::Won);
+
+        let replayed = decode(&encode(&game).unwrap()).unwrap();
+        assert_same_game(&replayed, &game);
+    }
+
+    #[test]
+    fn test_a_lost_game_survives_a_round_trip() {
+        let game = played(slump(GameDifficulty::Easy, 3), &["crane", "sleep", "plumb"]);
+        assert_eq!(game.game_status(), GameStatus::Lost);
+
+        let replayed = decode(&encode(&game).unwrap()).unwrap();
+        assert_same_game(&replayed, &game);
+        assert!(replayed
+            .row_states()
+            .iter()
+            .all(|row| *row == RowState::AlreadyGuessed));
+    }
+
+    #[test]
+    fn test_an_absurd_game_survives_a_round_trip() {
+        let mut game = Game::new(GameOptions {
+            difficulty: GameDifficulty::Absurd,
+            max_guesses: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("crane");
+        game.guess("stump");
+        assert_eq!(game.game_status(), GameStatus::Lost);
+
+        let replayed = decode(&encode(&game).unwrap()).unwrap();
+        assert_same_game(&replayed, &game);
+    }
+
+    #[test]
+    fn test_repeated_guesses_are_kept() {
+        let game = played(
+            GameOptions {
+                allow_duplicates: true,
+                ..slump(GameDifficulty::Easy, 6)
+            },
+            &["crane", "crane", "slump"],
+        );
+
+        let replayed = decode(&encode(&game).unwrap()).unwrap();
+        assert_same_game(&replayed, &game);
+    }
+
+    #[test]
+    fn test_a_game_that_was_given_up_survives_a_round_trip() {
+        let mut game = played(slump(GameDifficulty::Easy, 6), &["crane", "sleep"]);
+        game.give_up();
+
+        let replayed = decode(&encode(&game).unwrap()).unwrap();
+        assert_same_game(&replayed, &game);
+        assert!(replayed.was_given_up());
+    }
+
+    #[test]
+    fn test_only_finished_games_with_the_built_in_words_have_a_code() {
+        let in_progress = played(slump(GameDifficulty::Easy, 6), &["crane"]);
+        assert_eq!(encode(&in_progress), None);
+
+        let hidden = played(
+            GameOptions {
+                reveal_on_loss: false,
+                ..slump(GameDifficulty::Easy, 1)
+            },
+            &["crane"],
+        );
+        assert_eq!(encode(&hidden), None);
+
+        let made_up = played(
+            GameOptions {
+                skip_dictionary_check: true,
+                ..slump(GameDifficulty::Easy, 1)
+            },
+            &["xqzvj"],
+        );
+        assert_eq!(encode(&made_up), None);
+    }
+
+    #[test]
+    fn test_the_code_does_not_contain_the_words() {
+        let game = played(slump(GameDifficulty::Easy, 6), &["crane", "slump"]);
+        let code = encode(&game).unwrap().to_lowercase();
+        assert!(!code.contains("slump") && !code.contains("crane"));
+    }
+
+    #[test]
+    fn test_a_tampered_or_truncated_code_is_rejected() {
+        let game = played(slump(GameDifficulty::Easy, 6), &["crane", "slump"]);
+        let code = encode(&game).unwrap();
+
+        let mut tampered: Vec<char> = code.chars().collect();
+        tampered[9] = if tampered[9] == 'A' { 'B' } else { 'A' };
+        let tampered: String = tampered.into_iter().collect();
+        assert_eq!(decode(&tampered).err(), Some(GameError::InvalidReplayCode));
+
+        for length in 0..code.len() {
+            assert_eq!(
+                decode(&code[..length]).err(),
+                Some(GameError::InvalidReplayCode),
+                "{} was accepted",
+                &code[..length]
+            );
+        }
+    }
+
+    #[test]
+    fn test_garbage_is_rejected() {
+        for code in ["", "not a code!", "AAAA", "AAAAAAAAAAAA"] {
+            assert_eq!(decode(code).err(), Some(GameError::InvalidReplayCode));
+        }
+    }
+
+    #[test]
+    fn test_a_code_whose_guesses_do_not_replay_is_rejected() {
+        // a guess after the game was already won
+        let words = dictionary_list();
+        let index =
+            |word: &str| (words.iter().position(|w| w == word).unwrap() as u16).to_be_bytes();
+        let mut bytes = vec![VERSION, 0, 5, 6, 0];
+        bytes.extend_from_slice(&index("slump"));
+        bytes.push(2);
+        bytes.extend_from_slice(&index("slump"));
+        bytes.extend_from_slice(&index("crane"));
+        bytes.push(checksum(&bytes));
+
+        assert_eq!(
+            decode(&to_base64(&bytes)).err(),
+            Some(GameError::InvalidReplayCode)
+        );
+    }
+}
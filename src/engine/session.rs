@@ -0,0 +1,341 @@
+use crate::engine::{
+    Game, GameError, GameOptions, GameResult, GameStatus, GuessResult, HitAccuracy,
+};
+use std::collections::HashSet;
+
+// how many times we try to find an answer that no other board has before giving up and
+// allowing a repeat. Only tiny word lists ever get near it
+const MAX_ANSWER_ATTEMPTS: u64 = 1000;
+
+// Several boards played at once from the same guesses, like Dordle or Quordle. Every guess goes
+// to each board that isn't solved yet, and the session is only won once all of them are. A
+// single board session plays just like a Game.
+pub struct Session {
+    boards: Vec<Game>,
+    guesses_used: usize,
+}
+
+impl Session {
+    // every extra board gets one extra guess, so two boards get 7 guesses like Dordle, and four
+    // get 9 like Quordle
+    pub fn new(options: GameOptions, boards: usize) -> Result<Self, GameError> {
+        let options = GameOptions {
+            max_guesses: options.max_guesses + boards.saturating_sub(1),
+            ..options
+        };
+
+        let mut games: Vec<Game> = Vec::with_capacity(boards);
+        let mut answers = HashSet::new();
+        let mut offset = 0;
+        while games.len() < boards {
+            let game = Game::new(board_options(&options, offset))?;
+            if answers.insert(game.answer.clone()) || offset >= MAX_ANSWER_ATTEMPTS {
+                games.push(game);
+            }
+            offset += 1;
+        }
+
+        Ok(Session {
+            boards: games,
+            // every board starts with the same guesses on it
+            guesses_used: options.initial_guesses.len(),
+        })
+    }
+
+    // picks up a single board game that was saved part way through
+    pub fn resume(game: Game) -> Self {
+        Session {
+            guesses_used: game.guesses_used(),
+            boards: vec![game],
+        }
+    }
+
+    pub fn boards(&self) -> &[Game] {
+        self.boards.as_slice()
+    }
+
+    fn unsolved(&self) -> impl Iterator<Item = &Game> {
+        self.boards
+            .iter()
+            .filter(|game| game.game_status() == GameStatus::InProgress)
+    }
+
+    pub fn game_status(&self) -> GameStatus {
+        let statuses: Vec<GameStatus> = self.boards.iter().map(|g| g.game_status()).collect();
+        if statuses.contains(&GameStatus::Lost) {
+            GameStatus::Lost
+        } else if statuses.iter().all(|status| *status == GameStatus::Won) {
+            GameStatus::Won
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    pub fn guesses_used(&self) -> usize {
+        self.guesses_used
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.boards[0].max_guesses()
+    }
+
+    pub fn guess(&mut self, guess_input: &str) -> (GameStatus, GuessResult) {
+        if self.game_status() != GameStatus::InProgress {
+            return (self.game_status(), GuessResult::GameIsAlreadyOver);
+        }
+
+        // a guess has to be allowed on every board it goes to, or it isn't played at all.
+        // Otherwise the boards would end up with different numbers of guesses
+        if let Some(rejection) = self.unsolved().find_map(|game| game.rejection(guess_input)) {
+            return (self.game_status(), rejection);
+        }
+
+        for game in self
+            .boards
+            .iter_mut()
+            .filter(|game| game.game_status() == GameStatus::InProgress)
+        {
+            game.guess(guess_input);
+        }
+        self.guesses_used += 1;
+
+        (self.game_status(), GuessResult::Valid)
+    }
+
+    // every board that isn't solved yet is lost
+    pub fn give_up(&mut self) {
+        for game in self.boards.iter_mut() {
+            game.give_up();
+        }
+    }
+
+    // a letter on its own doesn't say which board it is for, so only a single board gets hints
+    pub fn hint(&mut self) -> Option<(usize, char)> {
+        match self.boards.as_mut_slice() {
+            [game] => game.hint(),
+            _ => None,
+        }
+    }
+
+    pub fn is_stuck(&self) -> bool {
+        self.unsolved().any(|game| game.is_stuck())
+    }
+
+    // The keyboard is shared, so a letter shows the best it has done on any board that is still
+    // being played. Solved boards don't need the letters any more.
+    pub fn get_letter_match_state(&self, letter: char) -> Option<HitAccuracy> {
+        let best = |games: &mut dyn Iterator<Item = &Game>| {
+            games
+                .filter_map(|game| game.get_letter_match_state(letter))
+                .reduce(|best, a| if a.is_better_than(best) { a } else { best })
+        };
+        match self.game_status() {
+            GameStatus::InProgress => best(&mut self.unsolved()),
+            _ => best(&mut self.boards.iter()),
+        }
+    }
+
+    // every board's answer, once the session is over. A solved board's answer is its last guess
+    pub fn answers(&self) -> Option<Vec<String>> {
+        if self.game_status() == GameStatus::InProgress {
+            return None;
+        }
+
+        self.boards
+            .iter()
+            .map(|game| match game.game_status() {
+                GameStatus::Won => game.last_guess().map(|guess| guess.word()),
+                _ => game.get_answer().ok(),
+            })
+            .collect()
+    }
+
+    // the whole session counts as one game in the stats. Once it's over every board is too,
+    // since the unsolved ones all run out of guesses together
+    pub fn result(&self) -> Option<GameResult> {
+        if self.game_status() == GameStatus::InProgress {
+            return None;
+        }
+
+        let results = self
+            .boards
+            .iter()
+            .map(|game| game.result())
+            .collect::<Option<Vec<_>>>()?;
+        Some(GameResult {
+            won: results.iter().all(|result| result.won),
+            guesses_used: self.guesses_used,
+            hints_used: results.iter().map(|result| result.hints_used).sum(),
+            solve_time: None,
+        })
+    }
+}
+
+// The first board is set up exactly as asked. The others move the seed or the day along, so a
+// seeded or daily session still gets different answers on every board, and the same ones next time.
+fn board_options(options: &GameOptions, offset: u64) -> GameOptions {
+    if offset == 0 {
+        return options.clone();
+    }
+
+    GameOptions {
+        answer: None,
+        seed: options.seed.map(|seed| seed.wrapping_add(offset)),
+        daily: options
+            .daily
+            .map(|day| day + chrono::Duration::days(offset as i64)),
+        ..options.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a session with known answers on every board
+    fn session(answers: &[&str]) -> Session {
+        Session {
+            boards: answers
+                .iter()
+                .map(|answer| {
+                    Game::new(GameOptions {
+                        answer: Some(answer.to_string()),
+                        max_guesses: 6 + answers.len() - 1,
+                        ..Default::default()
+                    })
+                    .unwrap()
+                })
+                .collect(),
+            guesses_used: 0,
+        }
+    }
+
+    #[test]
+    fn test_a_solved_board_stops_taking_guesses() {
+        let mut session = session(&["slump", "crane"]);
+
+        assert_eq!(
+            session.guess("slump"),
+            (GameStatus::InProgress, GuessResult::Valid)
+        );
+        assert_eq!(session.boards()[0].game_status(), GameStatus::Won);
+        assert_eq!(session.boards()[1].game_status(), GameStatus::InProgress);
+
+        session.guess("admit");
+        assert_eq!(
+            session.guess("crane"),
+            (GameStatus::Won, GuessResult::Valid)
+        );
+
+        assert_eq!(session.boards()[0].guesses().len(), 1);
+        assert_eq!(session.boards()[1].guesses().len(), 3);
+        assert_eq!(session.guesses_used(), 3);
+        assert_eq!(
+            session.result(),
+            Some(GameResult {
+                won: true,
+                guesses_used: 3,
+                hints_used: 0,
+                solve_time: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_a_session_is_lost_when_the_guesses_run_out_on_any_board() {
+        let mut session = session(&["slump", "crane"]);
+        session.guess("slump");
+        for word in ["admit", "adorn", "adult", "affix", "afire"] {
+            assert_eq!(session.game_status(), GameStatus::InProgress);
+            session.guess(word);
+        }
+        assert_eq!(session.game_status(), GameStatus::InProgress);
+
+        assert_eq!(
+            session.guess("aging"),
+            (GameStatus::Lost, GuessResult::Valid)
+        );
+        assert_eq!(session.boards()[1].game_status(), GameStatus::Lost);
+        assert_eq!(
+            session.answers(),
+            Some(vec!["slump".to_string(), "crane".to_string()])
+        );
+        assert_eq!(
+            session.guess("crane"),
+            (GameStatus::Lost, GuessResult::GameIsAlreadyOver)
+        );
+    }
+
+    #[test]
+    fn test_a_guess_one_board_rejects_is_not_played_anywhere() {
+        let mut session = session(&["slump", "crane"]);
+        assert_eq!(
+            session.guess("zzzzz"),
+            (GameStatus::InProgress, GuessResult::NotInDictionary)
+        );
+        assert!(session.boards().iter().all(|g| g.guesses().is_empty()));
+        assert_eq!(session.guesses_used(), 0);
+    }
+
+    #[test]
+    fn test_every_extra_board_gets_an_extra_guess() {
+        assert_eq!(
+            Session::new(GameOptions::default(), 1)
+                .unwrap()
+                .max_guesses(),
+            6
+        );
+        assert_eq!(
+            Session::new(GameOptions::default(), 2)
+                .unwrap()
+                .max_guesses(),
+            7
+        );
+        assert_eq!(
+            Session::new(GameOptions::default(), 4)
+                .unwrap()
+                .max_guesses(),
+            9
+        );
+    }
+
+    #[test]
+    fn test_every_board_gets_a_different_answer() {
+        let session = Session::new(
+            GameOptions {
+                answer: Some("slump".to_string()),
+                seed: Some(7),
+                ..Default::default()
+            },
+            4,
+        )
+        .unwrap();
+        let answers: HashSet<&String> = session.boards().iter().map(|g| &g.answer).collect();
+
+        assert_eq!(answers.len(), 4);
+        assert_eq!(session.boards()[0].answer, "slump");
+    }
+
+    #[test]
+    fn test_the_keyboard_shows_the_best_color_on_an_unsolved_board() {
+        let mut session = session(&["slump", "crane"]);
+        session.guess("clamp");
+
+        // 'l' is only in slump and 'a' is only in crane, but both are shown as found
+        assert_eq!(
+            session.get_letter_match_state('l'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        assert_eq!(
+            session.get_letter_match_state('a'),
+            Some(HitAccuracy::InRightPlace)
+        );
+        assert_eq!(session.get_letter_match_state('z'), None);
+    }
+
+    #[test]
+    fn test_only_a_single_board_gets_hints() {
+        assert!(session(&["slump"]).hint().is_some());
+        assert_eq!(session(&["slump", "crane"]).hint(), None);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::engine::game_error::GameError;
+use crate::engine::{Game, GameStatus, HitAccuracy, WordGuess};
+
+impl HitAccuracy {
+    pub fn emoji(&self) -> char {
+        match self {
+            HitAccuracy::InRightPlace => '🟩',
+            HitAccuracy::InWord => '🟨',
+            HitAccuracy::NotInWord => '⬛',
+        }
+    }
+
+    // the squares from a shared result. Light mode wordle uses a white square for letters
+    // that are not in the word
+    pub fn from_emoji(c: char) -> Option<HitAccuracy> {
+        match c {
+            '🟩' => Some(HitAccuracy::InRightPlace),
+            '🟨' => Some(HitAccuracy::InWord),
+            '⬛' | '⬜' => Some(HitAccuracy::NotInWord),
+            _ => None,
+        }
+    }
+}
+
+impl WordGuess {
+    pub fn emoji_row(&self) -> String {
+        self.letters()
+            .iter()
+            .map(|gl| gl.accuracy.emoji())
+            .collect()
+    }
+}
+
+impl Game {
+    // The grid people paste to show how they did without giving the answer away, e.g.
+    // "Wordlet 3/6 (difficulty 4/10)" followed by one row of squares per guess. A lost game is
+    // "X/6". The rating says how hard the word was without saying which word it was
+    pub fn share_text(&self) -> Option<String> {
+        let score = match self.game_status {
+            GameStatus::InProgress => return None,
+            GameStatus::Won => self.guesses.len().to_string(),
+            GameStatus::Lost => String::from("X"),
+        };
+        let rating = self
+            .answer_difficulty()
+            .map_or_else(String::new, |d| format!(" (difficulty {d}/10)"));
+
+        let rows: Vec<String> = self.guesses.iter().map(|g| g.emoji_row()).collect();
+        Some(format!(
+            "Wordlet {score}/{}{rating}\n\n{}",
+            self.max_guesses,
+            rows.join("\n")
+        ))
+    }
+}
+
+// Reads a pasted share grid back into rows of accuracies. Rows can be separated by newlines or
+// spaces, and lines without any squares (like the "Wordlet 3/6" header) are skipped.
+pub fn parse_share_grid(text: &str) -> Result<Vec<Vec<HitAccuracy>>, GameError> {
+    let rows: Vec<Vec<HitAccuracy>> = text
+        .split_whitespace()
+        .filter(|row| row.chars().any(|c| HitAccuracy::from_emoji(c).is_some()))
+        .map(|row| {
+            row.chars()
+                .map(HitAccuracy::from_emoji)
+                .collect::<Option<Vec<_>>>()
+                .ok_or(GameError::InvalidShareGrid)
+        })
+        .collect::<Result<_, _>>()?;
+
+    let row_length = rows.first().map(|row| row.len());
+    if row_length.is_none() || rows.iter().any(|row| Some(row.len()) != row_length) {
+        return Err(GameError::InvalidShareGrid);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GameOptions;
+    use HitAccuracy::*;
+
+    fn game_with_answer(answer: &str) -> Game {
+        Game::new(GameOptions {
+            answer: Some(answer.to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_squares_map_to_accuracies() {
+        assert_eq!(HitAccuracy::from_emoji('🟩'), Some(InRightPlace));
+        assert_eq!(HitAccuracy::from_emoji('🟨'), Some(InWord));
+        assert_eq!(HitAccuracy::from_emoji('⬛'), Some(NotInWord));
+        assert_eq!(HitAccuracy::from_emoji('⬜'), Some(NotInWord));
+        assert_eq!(HitAccuracy::from_emoji('a'), None);
+    }
+
+    #[test]
+    fn test_accuracies_round_trip_through_emoji() {
+        for accuracy in [InRightPlace, InWord, NotInWord] {
+            assert_eq!(HitAccuracy::from_emoji(accuracy.emoji()), Some(accuracy));
+        }
+    }
+
+    #[test]
+    fn test_there_is_nothing_to_share_while_the_game_is_in_progress() {
+        let mut game = game_with_answer("slump");
+        assert_eq!(game.share_text(), None);
+        game.guess("admit");
+        assert_eq!(game.share_text(), None);
+    }
+
+    #[test]
+    fn test_a_won_game_is_shared_with_its_score() {
+        let mut game = game_with_answer("slump");
+        game.guess("admit");
+        game.guess("plump");
+        game.guess("slump");
+
+        assert_eq!(
+            game.share_text(),
+            Some("Wordlet 3/6 (difficulty 6/10)\n\n⬛⬛🟨⬛⬛\n⬛🟩🟩🟩🟩\n🟩🟩🟩🟩🟩".to_string())
+        );
+    }
+
+    #[test]
+    fn test_a_lost_game_is_shared_as_an_x() {
+        let mut game = game_with_answer("slump");
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            game.guess(word);
+        }
+
+        let text = game.share_text().unwrap();
+        assert!(text.starts_with("Wordlet X/6 (difficulty 6/10)\n\n"));
+        assert_eq!(text.lines().nth(4), Some("⬛⬛🟩🟨⬛"));
+        assert_eq!(text.lines().count(), 8);
+    }
+
+    #[test]
+    fn test_the_score_is_out_of_the_guesses_the_board_has() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 4,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("admit");
+        game.guess("slump");
+        assert!(game
+            .share_text()
+            .unwrap()
+            .starts_with("Wordlet 2/4 (difficulty 6/10)\n\n"));
+
+        let mut lost = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 4,
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["admit", "adorn", "adult", "affix"] {
+            lost.guess(word);
+        }
+        let text = lost.share_text().unwrap();
+        assert!(text.starts_with("Wordlet X/4 (difficulty 6/10)\n\n"));
+        assert_eq!(text.lines().count(), 6);
+    }
+
+    #[test]
+    fn test_a_shared_grid_can_be_read_back() {
+        let mut game = game_with_answer("slump");
+        game.guess("plump");
+        game.guess("slump");
+
+        let rows: Vec<Vec<HitAccuracy>> = game.guesses().iter().map(|g| g.accuracies()).collect();
+        assert_eq!(parse_share_grid(&game.share_text().unwrap()), Ok(rows));
+    }
+
+    #[test]
+    fn test_a_pasted_grid_is_parsed_into_rows() {
+        let grid = "Wordlet 3/6\n\n⬛🟨⬛⬛⬛\n🟩⬛🟨🟨⬛\n🟩🟩🟩🟩🟩\n";
+
+        assert_eq!(
+            parse_share_grid(grid),
+            Ok(vec![
+                vec![NotInWord, InWord, NotInWord, NotInWord, NotInWord],
+                vec![InRightPlace, NotInWord, InWord, InWord, NotInWord],
+                vec![
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace
+                ],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_rows_can_be_separated_by_spaces_and_use_light_squares() {
+        assert_eq!(
+            parse_share_grid("⬜🟨⬜⬜⬜ 🟩🟩🟩🟩🟩"),
+            Ok(vec![
+                vec![NotInWord, InWord, NotInWord, NotInWord, NotInWord],
+                vec![
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace,
+                    InRightPlace
+                ],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_a_malformed_grid_is_rejected() {
+        assert_eq!(parse_share_grid(""), Err(GameError::InvalidShareGrid));
+        assert_eq!(
+            parse_share_grid("🟩🟩x🟩🟩"),
+            Err(GameError::InvalidShareGrid)
+        );
+        assert_eq!(
+            parse_share_grid("🟩🟩🟩🟩🟩\n🟩🟩🟩"),
+            Err(GameError::InvalidShareGrid)
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use crate::engine::game_error::GameError;
+use crate::engine::{json_string, Game, GameDifficulty, GameOptions, GameStatus, GuessResult};
+use clap::ArgEnum;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Everything needed to put a game back the way it was. Only the guesses are kept, not the
+// colors or the keyboard, because replaying the guesses against the same answer rebuilds those
+// exactly. The dictionary is loaded again on restore rather than saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSnapshot {
+    pub answer: String,
+    pub difficulty: GameDifficulty,
+    pub word_length: usize,
+    pub max_guesses: usize,
+    pub skip_dictionary_check: bool,
+    pub allow_duplicates: bool,
+    pub reveal_on_loss: bool,
+    pub answers_from_dictionary: bool,
+    // a game played with the player's own words is checked against the same words again
+    pub dictionary_path: Option<PathBuf>,
+    pub guesses: Vec<String>,
+    // how many of the guesses were on the board from the start, which hard mode didn't check
+    pub initial_guesses: usize,
+    // the positions that were given away by hints
+    pub hinted_positions: Vec<usize>,
+    pub game_status: GameStatus,
+}
+
+impl GameSnapshot {
+    // a missing, unreadable or corrupt file is the same as having no saved game
+    pub fn load(path: &Path) -> Option<GameSnapshot> {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| GameSnapshot::from_json(&json))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    // Like the stats, this is written by hand. A player's own word list can have anything in
+    // it, and so can the path to it, so the strings are escaped.
+    fn to_json(&self) -> String {
+        let guesses: Vec<String> = self.guesses.iter().map(|g| json_string(g)).collect();
+        let hinted: Vec<String> = self
+            .hinted_positions
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let dictionary = match &self.dictionary_path {
+            Some(path) => json_string(&path.to_string_lossy()),
+            None => String::from("null"),
+        };
+        format!(
+            "{{\"answer\":{},\"difficulty\":\"{}\",\"word_length\":{},\"max_guesses\":{},\"skip_dictionary_check\":{},\"allow_duplicates\":{},\"reveal_on_loss\":{},\"answers_from_dictionary\":{},\"dictionary\":{},\"guesses\":[{}],\"initial_guesses\":{},\"hinted_positions\":[{}],\"game_status\":\"{}\"}}",
+            json_string(&self.answer),
+            self.difficulty.name(),
+            self.word_length,
+            self.max_guesses,
+            self.skip_dictionary_check,
+            self.allow_duplicates,
+            self.reveal_on_loss,
+            self.answers_from_dictionary,
+            dictionary,
+            guesses.join(","),
+            self.initial_guesses,
+            hinted.join(","),
+            self.game_status.name()
+        )
+    }
+
+    fn from_json(json: &str) -> Option<GameSnapshot> {
+        let body = json.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let fields = top_level_fields(body)?;
+        let field = |name: &str| fields.get(name).copied();
+        let string = |name: &str| parse_json_string(field(name)?);
+        let list = |name: &str| {
+            let list = field(name)?.strip_prefix('[')?.strip_suffix(']')?;
+            match list.trim() {
+                "" => Some(vec![]),
+                list => Some(list.split(',').map(str::trim).collect::<Vec<_>>()),
+            }
+        };
+
+        Some(GameSnapshot {
+            answer: string("answer")?,
+            difficulty: GameDifficulty::from_str(&string("difficulty")?, false).ok()?,
+            word_length: field("word_length")?.parse().ok()?,
+            max_guesses: field("max_guesses")?.parse().ok()?,
+            skip_dictionary_check: field("skip_dictionary_check")?.parse().ok()?,
+            // and games saved before --allow-duplicates never had a word played twice
+            allow_duplicates: match field("allow_duplicates") {
+                None => false,
+                Some(allowed) => allowed.parse().ok()?,
+            },
+            reveal_on_loss: match field("reveal_on_loss") {
+                None => true,
+                Some(reveal) => reveal.parse().ok()?,
+            },
+            answers_from_dictionary: field("answers_from_dictionary")?.parse().ok()?,
+            // games saved before there was a --dictionary don't have one
+            dictionary_path: match field("dictionary") {
+                None | Some("null") => None,
+                Some(path) => Some(PathBuf::from(parse_json_string(path)?)),
+            },
+            guesses: list("guesses")?
+                .into_iter()
+                .map(parse_json_string)
+                .collect::<Option<_>>()?,
+            // and games saved before there were starting guesses didn't have any
+            initial_guesses: match field("initial_guesses") {
+                None => 0,
+                Some(count) => count.parse().ok()?,
+            },
+            hinted_positions: list("hinted_positions")?
+                .into_iter()
+                .map(|p| p.parse().ok())
+                .collect::<Option<_>>()?,
+            game_status: parse_status(&string("game_status")?)?,
+        })
+    }
+}
+
+// splits `"a":1,"b":[1,2]` into its fields, leaving commas inside lists and strings alone
+fn top_level_fields(body: &str) -> Option<HashMap<&str, &str>> {
+    let mut fields = HashMap::new();
+    let (mut depth, mut in_string, mut start) = (0, false, 0);
+    let mut escaped = false;
+
+    for (index, c) in body
+        .char_indices()
+        .chain(std::iter::once((body.len(), ',')))
+    {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '[' if !in_string => depth += 1,
+            ']' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                let (name, value) = body[start..index].split_once(':')?;
+                fields.insert(parse_json_name(name)?, value.trim());
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    Some(fields)
+}
+
+fn parse_json_name(name: &str) -> Option<&str> {
+    name.trim().strip_prefix('"')?.strip_suffix('"')
+}
+
+fn parse_json_string(value: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = parse_json_name(value)?.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                // json_string writes control characters as \u and four hex digits
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    unescaped.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                escaped => unescaped.push(escaped),
+            },
+            c => unescaped.push(c),
+        }
+    }
+    Some(unescaped)
+}
+
+fn parse_status(name: &str) -> Option<GameStatus> {
+    [GameStatus::Won, GameStatus::InProgress, GameStatus::Lost]
+        .into_iter()
+        .find(|status| status.name() == name)
+}
+
+impl Game {
+    pub fn snapshot(&self) -> GameSnapshot {
+        let mut hinted_positions: Vec<usize> = self.hinted_positions.iter().copied().collect();
+        hinted_positions.sort_unstable();
+
+        GameSnapshot {
+            answer: self.answer.clone(),
+            difficulty: self.difficulty,
+            word_length: self.word_length,
+            max_guesses: self.max_guesses,
+            skip_dictionary_check: self.skip_dictionary_check,
+            allow_duplicates: self.allow_duplicates,
+            reveal_on_loss: self.reveal_on_loss,
+            answers_from_dictionary: self.answers_from_dictionary,
+            dictionary_path: self.dictionary_path.clone(),
+            guesses: self.guesses.iter().map(|guess| guess.word()).collect(),
+            initial_guesses: self.initial_guesses,
+            hinted_positions,
+            game_status: self.game_status,
+        }
+    }
+
+    // A snapshot that doesn't replay cleanly, because it was edited or the dictionary has
+    // changed since, is an error rather than a different game
+    pub fn restore(snapshot: &GameSnapshot) -> Result<Game, GameError> {
+        let options = GameOptions {
+            answer: Some(snapshot.answer.clone()),
+            difficulty: snapshot.difficulty,
+            word_length: snapshot.word_length,
+            max_guesses: snapshot.max_guesses,
+            skip_dictionary_check: snapshot.skip_dictionary_check,
+            allow_duplicates: snapshot.allow_duplicates,
+            reveal_on_loss: snapshot.reveal_on_loss,
+            answers_from_dictionary: snapshot.answers_from_dictionary,
+            dictionary_path: snapshot.dictionary_path.clone(),
+            initial_guesses: snapshot
+                .guesses
+                .get(..snapshot.initial_guesses)
+                .ok_or(GameError::InvalidSnapshot)?
+                .to_vec(),
+            ..Default::default()
+        };
+        let mut game = Game::from_untrusted(options).map_err(|_| GameError::InvalidSnapshot)?;
+
+        for guess in &snapshot.guesses[snapshot.initial_guesses..] {
+            if game.guess(guess).1 != GuessResult::Valid {
+                return Err(GameError::InvalidSnapshot);
+            }
+        }
+
+        for position in &snapshot.hinted_positions {
+            if *position >= game.word_length || !game.hinted_positions.insert(*position) {
+                return Err(GameError::InvalidSnapshot);
+            }
+            game.hints_used += 1;
+        }
+
+        if game.game_status != snapshot.game_status || game.answer != snapshot.answer {
+            return Err(GameError::InvalidSnapshot);
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RowState;
+
+    fn game() -> Game {
+        Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_a_restored_game_picks_up_where_it_left_off() {
+        let mut game = game();
+        game.guess("clamp");
+        game.guess("plump");
+        game.hint();
+
+        let restored = Game::restore(&game.snapshot()).unwrap();
+
+        assert_eq!(restored.guesses(), game.guesses());
+        assert_eq!(restored.game_status(), GameStatus::InProgress);
+        assert_eq!(restored.row_states(), game.row_states());
+        assert_eq!(restored.row_states()[2], RowState::Current);
+        for letter in "clampu".chars() {
+            assert_eq!(
+                restored.get_letter_match_state(letter),
+                game.get_letter_match_state(letter)
+            );
+        }
+        assert_eq!(restored.snapshot(), game.snapshot());
+    }
+
+    #[test]
+    fn test_a_finished_game_can_be_restored() {
+        let mut game = game();
+        game.guess("slump");
+
+        let restored = Game::restore(&game.snapshot()).unwrap();
+        assert_eq!(restored.game_status(), GameStatus::Won);
+        assert_eq!(restored.guesses(), game.guesses());
+    }
+
+    #[test]
+    fn test_an_absurd_game_dodges_the_same_way_after_a_restore() {
+        let mut game = Game::new(GameOptions {
+            difficulty: GameDifficulty::Absurd,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("slump");
+        game.guess("crane");
+
+        let mut restored = Game::restore(&game.snapshot()).unwrap();
+        assert_eq!(restored.candidates_left(), game.candidates_left());
+
+        game.guess("doubt");
+        restored.guess("doubt");
+        assert_eq!(restored.guesses(), game.guesses());
+    }
+
+    #[test]
+    fn test_a_game_with_a_repeated_guess_is_restored() {
+        let mut repeated = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            allow_duplicates: true,
+            ..Default::default()
+        })
+        .unwrap();
+        repeated.guess("clamp");
+        repeated.guess("clamp");
+
+        let restored = Game::restore(&repeated.snapshot()).unwrap();
+        assert_eq!(restored.guesses(), repeated.guesses());
+
+        // an older save has no allow_duplicates, and can't have a repeated guess in it
+        let older = game()
+            .snapshot()
+            .to_json()
+            .replace("\"allow_duplicates\":false,", "");
+        assert_eq!(GameSnapshot::from_json(&older), Some(game().snapshot()));
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir()
+            .join(format!("wordlet-snapshot-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_starting_guesses_are_restored_without_the_hard_mode_check() {
+        let mut game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            difficulty: GameDifficulty::Hard,
+            initial_guesses: vec!["spilt".to_string(), "crane".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("slept");
+
+        let snapshot = game.snapshot();
+        assert_eq!(snapshot.initial_guesses, 2);
+        assert_eq!(
+            GameSnapshot::from_json(&snapshot.to_json()),
+            Some(snapshot.clone())
+        );
+
+        let restored = Game::restore(&snapshot).unwrap();
+        assert_eq!(restored.guesses(), game.guesses());
+        assert_eq!(restored.row_states(), game.row_states());
+    }
+
+    #[test]
+    fn test_a_snapshot_survives_a_save_and_load() {
+        let mut game = game();
+        game.guess("clamp");
+        game.hint();
+        game.hint();
+        let snapshot = game.snapshot();
+
+        let path = temp_path("round-trip.json");
+        snapshot.save(&path).unwrap();
+        assert_eq!(GameSnapshot::load(&path), Some(snapshot));
+
+        let fresh = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+        .snapshot();
+        fresh.save(&path).unwrap();
+        assert_eq!(GameSnapshot::load(&path), Some(fresh));
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_a_game_with_its_own_words_is_restored_with_them() {
+        let words = temp_path("my \"words\".txt");
+        fs::create_dir_all(words.parent().unwrap()).unwrap();
+        fs::write(&words, "katze\nhallo\n").unwrap();
+
+        let options = GameOptions {
+            answer: Some("katze".to_string()),
+            dictionary_path: Some(words.clone()),
+            ..Default::default()
+        };
+        let mut game = Game::new(GameOptions {
+            words: Some(options.validate().unwrap()),
+            ..options
+        })
+        .unwrap();
+        game.guess("hallo");
+
+        let path = temp_path("custom.json");
+        game.snapshot().save(&path).unwrap();
+        let snapshot = GameSnapshot::load(&path).unwrap();
+        assert_eq!(snapshot.dictionary_path, Some(words.clone()));
+
+        let restored = Game::restore(&snapshot).unwrap();
+        assert_eq!(restored.guesses(), game.guesses());
+        let _ = fs::remove_file(path);
+        let _ = fs::remove_file(words);
+    }
+
+    #[test]
+    fn test_strings_are_read_back_the_way_they_were_written() {
+        for value in ["crane", "don't", "a\"b\\c", "line\nbreak\ttab", "crème"] {
+            assert_eq!(
+                parse_json_string(&json_string(value)).as_deref(),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn test_a_missing_or_corrupt_snapshot_is_no_snapshot() {
+        assert_eq!(GameSnapshot::load(&temp_path("missing.json")), None);
+
+        let path = temp_path("corrupt.json");
+        let saved = game().snapshot().to_json();
+        for contents in [
+            "",
+            "not json",
+            &saved[..saved.len() / 2],
+            &saved.replace("\"hard\"", "\"extreme\""),
+            &saved.replace("\"word_length\":5", "\"word_length\":-5"),
+        ] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, contents).unwrap();
+            assert_eq!(GameSnapshot::load(&path), None);
+        }
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_a_snapshot_that_does_not_replay_is_rejected() {
+        let mut game = game();
+        game.guess("clamp");
+
+        let mut snapshot = game.snapshot();
+        snapshot.guesses.push("zzzzz".to_string());
+        assert_eq!(
+            Game::restore(&snapshot).err(),
+            Some(GameError::InvalidSnapshot)
+        );
+
+        let mut snapshot = game.snapshot();
+        snapshot.game_status = GameStatus::Won;
+        assert_eq!(
+            Game::restore(&snapshot).err(),
+            Some(GameError::InvalidSnapshot)
+        );
+
+        let mut snapshot = game.snapshot();
+        snapshot.hinted_positions = vec![7];
+        assert_eq!(
+            Game::restore(&snapshot).err(),
+            Some(GameError::InvalidSnapshot)
+        );
+    }
+}
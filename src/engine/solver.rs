@@ -0,0 +1,248 @@
+use crate::engine::utils::build_letter_counts;
+use crate::engine::word_source::WordSource;
+use crate::engine::{Game, HitAccuracy};
+use std::collections::HashMap;
+
+// The colors `guess` would get if `answer` was the answer, in two passes: greens take their
+// letters first, then yellows get whatever is left. The game's own colors come from this too,
+// through `evaluate`. A forced answer can be shorter than the guess, and the extra letters are
+// gray.
+pub fn feedback(guess: &str, answer: &str) -> Vec<HitAccuracy> {
+    let mut left = build_letter_counts(answer);
+    let answer: Vec<char> = answer.chars().collect();
+    let mut accuracies = vec![HitAccuracy::NotInWord; guess.chars().count()];
+
+    for (index, c) in guess.chars().enumerate() {
+        if answer.get(index) == Some(&c) {
+            accuracies[index] = HitAccuracy::InRightPlace;
+            *left.entry(c).or_insert(0) -= 1;
+        }
+    }
+
+    for (index, c) in guess.chars().enumerate() {
+        if accuracies[index] == HitAccuracy::InRightPlace {
+            continue;
+        }
+        if let Some(count) = left.get_mut(&c).filter(|count| **count > 0) {
+            *count -= 1;
+            accuracies[index] = HitAccuracy::InWord;
+        }
+    }
+
+    accuracies
+}
+
+// every dictionary word that would have given the same colors as each guess played so far,
+// which means any of them could still be the answer
+pub fn consistent_words(game: &Game) -> Vec<String> {
+    let mut words: Vec<String> = game
+        .dictionary
+        .words()
+        .filter(|word| word.chars().count() == game.word_length)
+        .filter(|word| {
+            game.guesses
+                .iter()
+                .all(|guess| feedback(&guess.word(), word) == guess.accuracies())
+        })
+        .cloned()
+        .collect();
+    words.sort();
+    words
+}
+
+// Ranks each candidate by how much we expect to learn from playing it: the entropy, in bits, of
+// the colors it would get across every word that could still be the answer. The best guess comes
+// first. Ties go to a word that could be the answer itself, and then to the first word
+// alphabetically, so the same clues always give the same suggestion.
+pub fn suggest(game: &Game, candidates: &[String]) -> Vec<(String, f64)> {
+    let possible = consistent_words(game);
+    let total = possible.len() as f64;
+
+    let mut scored: Vec<(String, f64, bool)> = candidates
+        .iter()
+        .map(|candidate| {
+            let mut patterns: HashMap<Vec<HitAccuracy>, usize> = HashMap::new();
+            for answer in possible.iter() {
+                *patterns.entry(feedback(candidate, answer)).or_insert(0) += 1;
+            }
+
+            let bits = patterns
+                .values()
+                .map(|count| {
+                    let p = *count as f64 / total;
+                    -p * p.log2()
+                })
+                .sum();
+            (candidate.clone(), bits, possible.contains(candidate))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.2.cmp(&a.2))
+            .then(a.0.cmp(&b.0))
+    });
+    scored
+        .into_iter()
+        .map(|(word, bits, _)| (word, bits))
+        .collect()
+}
+
+// A good first guess, before there are any colors to go on: the word whose letters are the most
+// common in their positions across the dictionary. Words that repeat a letter find out less, so
+// they only win when there's nothing else. Ties go to the first word alphabetically, so the same
+// dictionary always gives the same word.
+pub fn best_opener(dictionary: &dyn WordSource) -> String {
+    let mut counts: HashMap<(usize, char), usize> = HashMap::new();
+    for word in dictionary.words() {
+        for position in word.chars().enumerate() {
+            *counts.entry(position).or_insert(0) += 1;
+        }
+    }
+
+    let score = |word: &str| -> usize {
+        word.chars()
+            .enumerate()
+            .map(|position| counts.get(&position).copied().unwrap_or(0))
+            .sum()
+    };
+    let no_repeats = |word: &str| build_letter_counts(word).len() == word.chars().count();
+
+    dictionary
+        .words()
+        .map(|word| (no_repeats(word), score(word), word))
+        .max_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(b.2.cmp(a.2)))
+        .map(|(_, _, word)| word.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GameOptions;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+    use HitAccuracy::*;
+
+    fn game_with_answer(answer: &str) -> Game {
+        Game::new(GameOptions {
+            answer: Some(answer.to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_feedback_matches_the_game() {
+        assert_eq!(
+            feedback("admit", "slump"),
+            vec![NotInWord, NotInWord, InWord, NotInWord, NotInWord]
+        );
+        // 'alien' only has one e, and the green one takes it
+        assert_eq!(
+            feedback("sleep", "alien"),
+            vec![NotInWord, InRightPlace, NotInWord, InRightPlace, NotInWord]
+        );
+
+        let mut game = game_with_answer("alien");
+        game.guess("sleep");
+        assert_eq!(game.guesses()[0].accuracies(), feedback("sleep", "alien"));
+    }
+
+    #[test]
+    fn test_the_candidates_shrink_with_every_guess() {
+        let mut game = game_with_answer("slump");
+        let everything = consistent_words(&game);
+        assert_eq!(everything.len(), game.dictionary.words().count());
+
+        game.guess("admit");
+        let after_one = consistent_words(&game);
+        assert!(after_one.len() < everything.len());
+        assert!(after_one
+            .iter()
+            .all(|w| w.contains('m') && !w.contains('a')));
+
+        game.guess("slept");
+        let after_two = consistent_words(&game);
+        assert!(after_two.len() < after_one.len());
+        assert!(after_two.contains(&"slump".to_string()));
+        assert!(after_two.iter().all(|w| w.starts_with("sl")));
+    }
+
+    #[test]
+    fn test_a_guess_that_splits_the_candidates_ranks_higher() {
+        let mut game = game_with_answer("slump");
+        game.guess("admit");
+        game.guess("slept");
+
+        let candidates = consistent_words(&game);
+        let ranked = suggest(&game, &candidates);
+        assert_eq!(ranked.len(), candidates.len());
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        // a word we already know isn't the answer tells us nothing
+        let ranked = suggest(&game, &["slump".to_string(), "admit".to_string()]);
+        assert_eq!(ranked[1], ("admit".to_string(), 0.0));
+    }
+
+    fn words(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_equally_good_guesses_are_ranked_the_same_way_every_time() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let game = Game::with_words(
+            GameOptions {
+                answer: Some("bears".to_string()),
+                ..Default::default()
+            },
+            Box::new(words(&["bears", "pears"])),
+            &mut rng,
+        )
+        .unwrap();
+
+        // each of these tells bears and pears apart, so they're all worth exactly one bit. bloke
+        // comes first alphabetically, but it can't be the answer
+        let candidates: Vec<String> = ["pears", "bloke", "bears"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let ranked = suggest(&game, &candidates);
+        assert!(ranked.iter().all(|(_, bits)| *bits == 1.0));
+        let order: Vec<&str> = ranked.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(order, ["bears", "pears", "bloke"]);
+    }
+
+    #[test]
+    fn test_the_opener_has_the_most_common_letters_for_their_positions() {
+        // position by position, c starts 3 words, a is second in 4, r third in 4 and e last in 4,
+        // so care scores 3+4+4+4 = 15, bare 14, core and cart 12, and bake 11
+        let dictionary = words(&["care", "bare", "core", "cart", "bake"]);
+        assert_eq!(best_opener(&dictionary), "care");
+    }
+
+    #[test]
+    fn test_the_opener_avoids_repeated_letters_and_is_always_the_same() {
+        // seem and seed score 4+2+4+2 = 12, but they both repeat e. stem and sled score
+        // 4+1+4+2 = 11, and sled comes first alphabetically
+        let dictionary = words(&["seem", "seed", "stem", "sled"]);
+        assert_eq!(best_opener(&dictionary), "sled");
+
+        // with nothing but repeats, the tie goes to the first word alphabetically
+        assert_eq!(best_opener(&words(&["seem", "seed"])), "seed");
+        assert_eq!(best_opener(&HashSet::new()), "");
+        for _ in 0..5 {
+            assert_eq!(best_opener(&dictionary), "sled");
+        }
+    }
+
+    #[test]
+    fn test_the_built_in_dictionary_has_an_opener() {
+        let game = game_with_answer("slump");
+        let opener = best_opener(game.dictionary.as_ref());
+        assert_eq!(opener.chars().count(), 5);
+        assert_eq!(opener, game.opener());
+    }
+}
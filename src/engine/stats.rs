@@ -0,0 +1,310 @@
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+// How one finished game went, which is all the stats need to know about it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameResult {
+    pub won: bool,
+    pub guesses_used: usize,
+    pub hints_used: usize,
+    // the game doesn't keep time, so this is filled in by whoever is showing the board
+    pub solve_time: Option<Duration>,
+}
+
+// The running totals across every game played, like the stats screen in wordle
+#[derive(Debug, Default, PartialEq)]
+pub struct Stats {
+    pub games_played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    // games where the player asked for at least one hint
+    pub assisted: u32,
+    // how many games were won in one guess, two guesses, and so on. Losses aren't counted here
+    pub distribution: Vec<u32>,
+    // the fastest win, to the second
+    pub best_time: Option<Duration>,
+    // the day of the last daily puzzle that was finished, so it isn't played twice
+    pub last_daily: Option<NaiveDate>,
+}
+
+impl Stats {
+    pub fn record(&mut self, result: GameResult) {
+        self.games_played += 1;
+        if result.hints_used > 0 {
+            self.assisted += 1;
+        }
+
+        if !result.won {
+            self.current_streak = 0;
+            return;
+        }
+
+        self.wins += 1;
+        self.current_streak += 1;
+        if let Some(time) = result.solve_time {
+            let time = Duration::from_secs(time.as_secs());
+            self.best_time = Some(self.best_time.map_or(time, |best| best.min(time)));
+        }
+        self.max_streak = self.max_streak.max(self.current_streak);
+
+        // the number of guesses can be changed, so the buckets grow as they are needed
+        if self.distribution.len() < result.guesses_used {
+            self.distribution.resize(result.guesses_used, 0);
+        }
+        self.distribution[result.guesses_used - 1] += 1;
+    }
+
+    pub fn win_percentage(&self) -> u32 {
+        match self.games_played {
+            0 => 0,
+            played => self.wins * 100 / played,
+        }
+    }
+
+    // A missing or unreadable file just means starting over. Losing a streak is annoying, but
+    // it's not worth refusing to play over.
+    pub fn load(path: &Path) -> Stats {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|json| Stats::from_json(&json))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    // the stats are only a handful of numbers, so we write the JSON by hand
+    fn to_json(&self) -> String {
+        let distribution: Vec<String> = self.distribution.iter().map(|n| n.to_string()).collect();
+        format!(
+            "{{\"games_played\":{},\"wins\":{},\"current_streak\":{},\"max_streak\":{},\"assisted\":{},{}{}\"distribution\":[{}]}}",
+            self.games_played,
+            self.wins,
+            self.current_streak,
+            self.max_streak,
+            self.assisted,
+            self.best_time
+                .map_or_else(String::new, |t| format!("\"best_time_secs\":{},", t.as_secs())),
+            // written as a number like 20220128, since the parser below only reads numbers
+            self.last_daily.map_or_else(String::new, |day| format!(
+                "\"last_daily\":{},",
+                day.year() * 10000 + day.month() as i32 * 100 + day.day() as i32
+            )),
+            distribution.join(",")
+        )
+    }
+
+    // Reads back what to_json wrote. Files from before best_time_secs and last_daily were added
+    // don't have them, but everything else has to be there.
+    fn from_json(json: &str) -> Option<Stats> {
+        let body = json.trim().strip_prefix('{')?.strip_suffix('}')?;
+        let (numbers, distribution) = body.split_once(",\"distribution\":")?;
+
+        let fields = numbers
+            .split(',')
+            .map(|field| {
+                let (name, value) = field.split_once(':')?;
+                Some((
+                    name.trim().trim_matches('"'),
+                    value.trim().parse::<u64>().ok()?,
+                ))
+            })
+            .collect::<Option<HashMap<_, _>>>()?;
+        let field = |name: &str| fields.get(name).and_then(|v| u32::try_from(*v).ok());
+
+        Some(Stats {
+            games_played: field("games_played")?,
+            wins: field("wins")?,
+            current_streak: field("current_streak")?,
+            max_streak: field("max_streak")?,
+            assisted: field("assisted")?,
+            distribution: {
+                let list = distribution.trim().strip_prefix('[')?.strip_suffix(']')?;
+                match list.trim() {
+                    "" => vec![],
+                    list => list
+                        .split(',')
+                        .map(|n| n.trim().parse().ok())
+                        .collect::<Option<_>>()?,
+                }
+            },
+            best_time: fields
+                .get("best_time_secs")
+                .map(|secs| Duration::from_secs(*secs)),
+            last_daily: match fields.get("last_daily") {
+                Some(day) => Some(NaiveDate::from_ymd_opt(
+                    i32::try_from(day / 10000).ok()?,
+                    (day / 100 % 100) as u32,
+                    (day % 100) as u32,
+                )?),
+                None => None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn won_in(guesses_used: usize) -> GameResult {
+        GameResult {
+            won: true,
+            guesses_used,
+            hints_used: 0,
+            solve_time: None,
+        }
+    }
+
+    fn lost() -> GameResult {
+        GameResult {
+            won: false,
+            guesses_used: 6,
+            hints_used: 0,
+            solve_time: Some(Duration::from_secs(10)),
+        }
+    }
+
+    #[test]
+    fn test_a_loss_resets_the_current_streak_but_not_the_max() {
+        let mut stats = Stats::default();
+        stats.record(won_in(3));
+        stats.record(won_in(4));
+        stats.record(won_in(2));
+        stats.record(lost());
+        stats.record(won_in(5));
+
+        assert_eq!(stats.games_played, 5);
+        assert_eq!(stats.wins, 4);
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.max_streak, 3);
+        assert_eq!(stats.win_percentage(), 80);
+    }
+
+    #[test]
+    fn test_wins_are_bucketed_by_the_number_of_guesses() {
+        let mut stats = Stats::default();
+        stats.record(won_in(4));
+        stats.record(won_in(2));
+        stats.record(won_in(4));
+        stats.record(lost());
+
+        assert_eq!(stats.distribution, vec![0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_games_with_hints_are_counted_as_assisted() {
+        let mut stats = Stats::default();
+        stats.record(GameResult {
+            hints_used: 2,
+            ..won_in(3)
+        });
+        stats.record(won_in(4));
+
+        assert_eq!(stats.assisted, 1);
+        assert_eq!(stats.wins, 2);
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("wordlet-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_stats_survive_a_save_and_load() {
+        let mut stats = Stats::default();
+        stats.record(won_in(3));
+        stats.record(lost());
+        stats.record(GameResult {
+            hints_used: 1,
+            solve_time: Some(Duration::from_secs(95)),
+            ..won_in(5)
+        });
+
+        let path = temp_path("round-trip/stats.json");
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path), stats);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_the_last_daily_puzzle_survives_a_save_and_load() {
+        let stats = Stats {
+            last_daily: Some(NaiveDate::from_ymd(2022, 1, 28)),
+            ..Default::default()
+        };
+        assert!(stats.to_json().contains("\"last_daily\":20220128,"));
+
+        let path = temp_path("daily.json");
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path), stats);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_empty_stats_survive_a_save_and_load() {
+        let path = temp_path("empty.json");
+        Stats::default().save(&path).unwrap();
+        assert_eq!(Stats::load(&path), Stats::default());
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_a_missing_or_corrupt_file_starts_from_empty_stats() {
+        assert_eq!(Stats::load(&temp_path("missing.json")), Stats::default());
+
+        let path = temp_path("corrupt.json");
+        for contents in ["", "not json", "{\"games_played\":3}", "{\"games_played\":-1,\"wins\":0,\"current_streak\":0,\"max_streak\":0,\"assisted\":0,\"distribution\":[]}"] {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, contents).unwrap();
+            assert_eq!(Stats::load(&path), Stats::default());
+        }
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_the_best_time_is_the_fastest_win() {
+        let won_after = |secs| GameResult {
+            solve_time: Some(Duration::from_millis(secs)),
+            ..won_in(3)
+        };
+        let mut stats = Stats::default();
+        stats.record(won_after(90_400));
+        stats.record(won_after(45_900));
+        stats.record(won_after(60_000));
+        // losing faster doesn't count
+        stats.record(lost());
+
+        assert_eq!(stats.best_time, Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn test_stats_saved_before_best_times_still_load() {
+        let path = temp_path("old.json");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "{\"games_played\":2,\"wins\":1,\"current_streak\":0,\"max_streak\":1,\"assisted\":0,\"distribution\":[0,1]}").unwrap();
+
+        let stats = Stats::load(&path);
+        assert_eq!(stats.games_played, 2);
+        assert_eq!(stats.distribution, vec![0, 1]);
+        assert_eq!(stats.best_time, None);
+        assert_eq!(stats.last_daily, None);
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_no_games_is_a_zero_win_percentage() {
+        assert_eq!(Stats::default().win_percentage(), 0);
+    }
+}
@@ -1,19 +1,157 @@
-use crate::engine::words::dictionary_words;
+use crate::engine::game_error::GameError;
+use crate::engine::words::{answer_words, dictionary_words};
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
 use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
-pub fn dictionary() -> HashSet<String> {
-    let mut dict = HashSet::new();
-    for w in dictionary_words() {
-        dict.insert(w);
+// Every game with the built-in words shares these, so they're only built the first time they're
+// needed
+static DICTIONARY_LIST: OnceLock<Vec<String>> = OnceLock::new();
+static DICTIONARY: OnceLock<Arc<HashSet<String>>> = OnceLock::new();
+static ANSWERS: OnceLock<Arc<HashSet<String>>> = OnceLock::new();
+
+// the built-in words in their fixed order, so a word's position can stand for it in a code
+pub fn dictionary_list() -> &'static [String] {
+    DICTIONARY_LIST.get_or_init(dictionary_words)
+}
+
+pub fn dictionary() -> Arc<HashSet<String>> {
+    DICTIONARY
+        .get_or_init(|| Arc::new(dictionary_list().iter().cloned().collect()))
+        .clone()
+}
+
+const MAX_DICTIONARY_BYTES: u64 = 100_000_000;
+
+// a word list read from a file, and how many of its lines were left out
+#[derive(Debug, PartialEq)]
+pub struct WordList {
+    pub words: HashSet<String>,
+    pub skipped: usize,
+}
+
+// A word list of the player's own, one word per line. A file that isn't text is an error, but
+// anything else is cleaned up, since people make these by hand or copy them from somewhere
+pub fn load_dictionary(path: &Path, length: usize) -> Result<WordList, GameError> {
+    let unreadable = |reason: String| GameError::UnreadableDictionary(path.to_path_buf(), reason);
+    // a file this big is almost certainly not a word list, and would take a long time to read
+    let size = fs::metadata(path)
+        .map_err(|e| unreadable(e.to_string()))?
+        .len();
+    if size > MAX_DICTIONARY_BYTES {
+        return Err(unreadable(format!(
+            "it is over {} MB",
+            MAX_DICTIONARY_BYTES / 1_000_000
+        )));
     }
-    dict
+
+    let contents = fs::read_to_string(path).map_err(|e| unreadable(e.to_string()))?;
+    Ok(clean_word_list(&contents, length))
 }
 
-pub fn get_random_word() -> String {
-    let dict = dictionary();
-    let list = Vec::from_iter(dict.iter());
-    list.choose(&mut rand::thread_rng()).unwrap().to_string()
+// Only words of the length being played are kept, lowercased and without stray spaces or
+// Windows line endings. A word is letters, with apostrophes or hyphens after the first one, like
+// "don't" or "x-ray". A line with anything else in it, like two words or a comma, is skipped
+// along with the words that are too long or too short. Blank lines are just spacing, so they
+// aren't counted as skipped
+pub fn clean_word_list(contents: &str, length: usize) -> WordList {
+    let mut list = WordList {
+        words: HashSet::new(),
+        skipped: 0,
+    };
+
+    for line in contents.lines() {
+        let word = line.trim().to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        if word.chars().count() == length && is_word(&word) {
+            list.words.insert(word);
+        } else {
+            list.skipped += 1;
+        }
+    }
+
+    list
+}
+
+fn is_word(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(char::is_alphabetic)
+        && chars.all(|c| c.is_alphabetic() || c == '\'' || c == '-')
+}
+
+// the words an answer can be. These are all in the dictionary too, so they can always be guessed
+pub fn answers() -> Arc<HashSet<String>> {
+    ANSWERS
+        .get_or_init(|| Arc::new(answer_words().into_iter().collect()))
+        .clone()
+}
+
+// answers are picked from the answer list, so that's the one that needs a word of this length
+pub fn has_words_of_length<'a>(words: impl IntoIterator<Item = &'a String>, length: usize) -> bool {
+    words.into_iter().any(|w| w.chars().count() == length)
+}
+
+// With a seed, every game gets the same random numbers, so it picks the same answer every time
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Picks a word of this length that isn't in `played`. Once every word has been played it
+// starts over, skipping only the last one so it doesn't come up twice in a row. The same rng
+// state always picks the same word. Callers check has_words_of_length first
+pub fn get_random_word<'a>(
+    words: impl IntoIterator<Item = &'a String>,
+    length: usize,
+    rng: &mut dyn RngCore,
+    played: &[String],
+) -> String {
+    let mut list = sorted_words_of_length(words, length);
+    let skipped = match list.iter().all(|word| played.contains(*word)) {
+        true => played.last().map_or(&[][..], std::slice::from_ref),
+        false => played,
+    };
+    if list.iter().any(|word| !skipped.contains(*word)) {
+        list.retain(|word| !skipped.contains(*word));
+    }
+    list.choose(rng).unwrap().to_string()
+}
+
+// the day of the first wordle puzzle. Every day after it moves one word further down the list
+fn daily_epoch() -> NaiveDate {
+    NaiveDate::from_ymd(2021, 6, 19)
+}
+
+// everyone playing on the same day gets the same word, whatever time they start
+pub fn get_daily_word<'a>(
+    words: impl IntoIterator<Item = &'a String>,
+    length: usize,
+    day: NaiveDate,
+) -> String {
+    let list = sorted_words_of_length(words, length);
+    let days = day.signed_duration_since(daily_epoch()).num_days();
+    let index = days.rem_euclid(list.len() as i64) as usize;
+    list[index].to_string()
+}
+
+// a HashSet comes out in a different order on every run, so anything that has to pick the same
+// word twice needs them sorted first
+pub fn sorted_words_of_length<'a>(
+    words: impl IntoIterator<Item = &'a String>,
+    length: usize,
+) -> Vec<&'a String> {
+    let mut list = Vec::from_iter(words.into_iter().filter(|w| w.chars().count() == length));
+    list.sort();
+    list
 }
 
 pub fn build_letter_counts(word: &str) -> HashMap<char, usize> {
@@ -23,9 +161,270 @@ pub fn build_letter_counts(word: &str) -> HashMap<char, usize> {
             Some(v) => *v += 1,
             None => {
                 counts.insert(c, 1);
-                ()
             }
         };
     }
     counts
 }
+
+// English letters, from the most common to the least common
+const LETTERS_BY_FREQUENCY: &str = "etaoinshrdlcumwfgypbvkjxqz";
+
+// Rates a word from 1 (easy) to 10 (hard). Rare letters make a word harder to find, and so do
+// repeated letters, because players tend to guess words with five different letters.
+pub fn estimate_difficulty(word: &str) -> u8 {
+    let rarity: usize = word
+        .chars()
+        .map(|c| {
+            LETTERS_BY_FREQUENCY
+                .find(c)
+                .unwrap_or(LETTERS_BY_FREQUENCY.len())
+        })
+        .sum();
+
+    let repeats: usize = build_letter_counts(word)
+        .values()
+        .map(|count| count - 1)
+        .sum();
+
+    let score = (rarity + repeats * 15) * 9 / 100;
+    (1 + score.min(9)) as u8
+}
+
+// Guesses typed into --batch and the paths of word lists can have quotes, backslashes or control
+// characters in them, and all of those have to be escaped in a JSON string
+pub fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// Short glosses for some of the answers, one "word<tab>meaning" per line. The file is built into
+// the binary, so there's nothing to go missing at runtime
+const DEFINITIONS: &str = include_str!("definitions.txt");
+
+pub fn definition(word: &str) -> Option<String> {
+    find_definition(DEFINITIONS, word)
+}
+
+// lines without a tab or without a meaning are ignored, so a bad line can't break a lookup
+fn find_definition(contents: &str, word: &str) -> Option<String> {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .find(|(w, _)| w.trim().eq_ignore_ascii_case(word))
+        .map(|(_, meaning)| meaning.trim().to_string())
+        .filter(|meaning| !meaning.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_strings_escape_quotes_backslashes_and_control_characters() {
+        assert_eq!(json_string("crane"), "\"crane\"");
+        assert_eq!(
+            json_string("a\"b\\c\nd\u{7f}"),
+            "\"a\\\"b\\\\c\\u000ad\\u007f\""
+        );
+    }
+
+    #[test]
+    fn test_random_words_have_the_requested_length() {
+        assert_eq!(
+            get_random_word(dictionary().iter(), 5, &mut seeded_rng(None), &[])
+                .chars()
+                .count(),
+            5
+        );
+        assert!(has_words_of_length(answers().iter(), 5));
+        assert!(!has_words_of_length(answers().iter(), 4));
+    }
+
+    #[test]
+    fn test_the_same_seed_picks_the_same_word() {
+        let words = answers();
+        let word = get_random_word(words.iter(), 5, &mut seeded_rng(Some(42)), &[]);
+        for _ in 0..10 {
+            assert_eq!(
+                get_random_word(answers().iter(), 5, &mut seeded_rng(Some(42)), &[]),
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn test_played_words_are_skipped_until_there_are_none_left() {
+        let pool = words(&["crane", "slump", "adieu"]);
+        let played = ["crane".to_string(), "adieu".to_string()];
+        for _ in 0..10 {
+            assert_eq!(
+                get_random_word(&pool, 5, &mut seeded_rng(None), &played),
+                "slump"
+            );
+        }
+
+        // the last word played is still skipped when starting over,
+        let everything = ["crane", "adieu", "slump"].map(String::from);
+        for _ in 0..10 {
+            assert_ne!(
+                get_random_word(&pool, 5, &mut seeded_rng(None), &everything),
+                "slump"
+            );
+        }
+        // unless it's the only word there is
+        let one = words(&["slump"]);
+        assert_eq!(
+            get_random_word(&one, 5, &mut seeded_rng(None), &everything),
+            "slump"
+        );
+    }
+
+    #[test]
+    fn test_the_daily_word_is_picked_by_the_date() {
+        let words = answers();
+        let mut sorted = answer_words();
+        sorted.sort();
+
+        let first_day = NaiveDate::from_ymd(2021, 6, 19);
+        assert_eq!(get_daily_word(words.iter(), 5, first_day), sorted[0]);
+        assert_eq!(
+            get_daily_word(words.iter(), 5, NaiveDate::from_ymd(2021, 6, 22)),
+            sorted[3]
+        );
+
+        // the list starts over once every word has been used
+        let wrapped = first_day + chrono::Duration::days(sorted.len() as i64 + 1);
+        assert_eq!(get_daily_word(words.iter(), 5, wrapped), sorted[1]);
+    }
+
+    #[test]
+    fn test_every_answer_can_be_guessed() {
+        let dictionary = dictionary();
+        let answers = answers();
+        assert!(answers.len() < dictionary.len());
+        assert!(answers.iter().all(|answer| dictionary.contains(answer)));
+    }
+
+    #[test]
+    fn test_common_words_are_easier_than_obscure_ones() {
+        assert!(estimate_difficulty("stare") < estimate_difficulty("jazzy"));
+        assert!(estimate_difficulty("arise") < estimate_difficulty("fuzzy"));
+        assert!(estimate_difficulty("tears") < estimate_difficulty("vivid"));
+    }
+
+    #[test]
+    fn test_repeated_letters_make_a_word_harder() {
+        assert!(estimate_difficulty("slump") < estimate_difficulty("mummy"));
+    }
+
+    #[test]
+    fn test_difficulty_is_between_one_and_ten() {
+        assert_eq!(estimate_difficulty("etaoi"), 1);
+        assert_eq!(estimate_difficulty("zzzzz"), 10);
+    }
+
+    fn words(words: &[&str]) -> HashSet<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_a_custom_dictionary_is_cleaned_up_on_load() {
+        let path = std::env::temp_dir().join(format!("wordlet-words-{}.txt", std::process::id()));
+        fs::write(&path, "  Hello\nworld \n\nHELLO\r\n").unwrap();
+
+        let list = load_dictionary(&path, 5).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(list.words, words(&["hello", "world"]));
+        assert_eq!(list.skipped, 0);
+    }
+
+    #[test]
+    fn test_words_that_cannot_be_played_are_skipped_and_counted() {
+        let list = clean_word_list("crane\n\n   \nslum\nSLUMP\r\nno way\nfour,\nchâté\n", 5);
+        assert_eq!(list.words, words(&["crane", "slump", "châté"]));
+        assert_eq!(list.skipped, 3);
+
+        let list = clean_word_list("don't\nX-Ray\n'twas\n-ward\n", 5);
+        assert_eq!(list.words, words(&["don't", "x-ray"]));
+        assert_eq!(list.skipped, 2);
+
+        let list = clean_word_list("crane\nslum\n", 4);
+        assert_eq!(list.words, words(&["slum"]));
+        assert_eq!(list.skipped, 1);
+    }
+
+    #[test]
+    fn test_a_dictionary_that_is_not_text_is_an_error() {
+        let path = std::env::temp_dir().join(format!("wordlet-binary-{}.txt", std::process::id()));
+        fs::write(&path, [0x63, 0xff, 0xfe, 0x0a]).unwrap();
+
+        let result = load_dictionary(&path, 5);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(result, Err(GameError::UnreadableDictionary(..))));
+    }
+
+    #[test]
+    fn test_a_dictionary_that_is_too_big_is_not_read() {
+        let path = std::env::temp_dir().join(format!("wordlet-huge-{}.txt", std::process::id()));
+        // set_len leaves a sparse file, so nothing that size is written
+        let file = fs::File::create(&path).unwrap();
+        file.set_len(MAX_DICTIONARY_BYTES + 1).unwrap();
+
+        let result = load_dictionary(&path, 5);
+        let _ = fs::remove_file(&path);
+        assert!(matches!(
+            result,
+            Err(GameError::UnreadableDictionary(_, reason)) if reason == "it is over 100 MB"
+        ));
+    }
+
+    #[test]
+    fn test_a_missing_dictionary_is_an_error() {
+        let path = Path::new("/no/such/dir/words.txt");
+        assert!(matches!(
+            load_dictionary(path, 5),
+            Err(GameError::UnreadableDictionary(p, _)) if p == path
+        ));
+    }
+
+    #[test]
+    fn test_some_answers_have_a_definition() {
+        assert_eq!(
+            definition("slump").as_deref(),
+            Some("a sudden fall in value or activity; also, to sit heavily")
+        );
+        assert_eq!(definition("about"), None);
+        assert_eq!(definition(""), None);
+    }
+
+    #[test]
+    fn test_every_definition_is_for_a_playable_word() {
+        let dictionary = dictionary();
+        for line in DEFINITIONS.lines() {
+            let (word, meaning) = line.split_once('\t').unwrap();
+            assert!(dictionary.contains(word), "{word} is not in the dictionary");
+            assert!(!meaning.trim().is_empty(), "{word} has no meaning");
+        }
+    }
+
+    #[test]
+    fn test_bad_definition_lines_are_ignored() {
+        assert_eq!(find_definition("", "slump"), None);
+        let contents = "slump\nslump\t  \ncrane no tab\nCrane\ta bird \n";
+        assert_eq!(find_definition(contents, "slump"), None);
+        assert_eq!(
+            find_definition(contents, "crane").as_deref(),
+            Some("a bird")
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::engine::game_error::GameError;
+use crate::engine::utils;
+use rand::RngCore;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// The words a guess is checked against. A HashSet is the quickest to look a word up in, and is
+// what Wordlet uses unless told otherwise. A sorted list takes less memory, which only matters
+// for a very long list of the player's own, and is slower to look up, though a binary search is
+// still quick enough to check a guess.
+//
+// The game only knows its words through this, so a test can play with a handful of its own.
+pub trait WordSource {
+    fn contains(&self, word: &str) -> bool;
+    // every word that can be guessed
+    fn words(&self) -> Box<dyn Iterator<Item = &String> + '_>;
+
+    // the words the answer can be. A list of the player's own has no separate answers
+    fn answers(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        self.words()
+    }
+
+    fn random_answer(&self, length: usize, rng: &mut dyn RngCore, played: &[String]) -> String {
+        utils::get_random_word(self.answers(), length, rng, played)
+    }
+}
+
+impl WordSource for HashSet<String> {
+    fn contains(&self, word: &str) -> bool {
+        HashSet::contains(self, word)
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        Box::new(self.iter())
+    }
+}
+
+// shared word lists, so that every game made from the same options uses the same copy
+impl<T: WordSource + ?Sized> WordSource for Arc<T> {
+    fn contains(&self, word: &str) -> bool {
+        (**self).contains(word)
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        (**self).words()
+    }
+}
+
+// The words that can be guessed, along with the shorter list the answer is picked from. These
+// are loaded once, by GameOptions::validate, and every game played with the same options
+// shares them. A list of the player's own is both, and remembers where it came from
+#[derive(Clone)]
+pub struct WordLists {
+    pub dictionary: Arc<dyn WordSource>,
+    pub answers: Arc<dyn WordSource>,
+    pub path: Option<PathBuf>,
+    // the lines of the player's file that weren't words of the right length
+    pub skipped: usize,
+}
+
+impl WordLists {
+    // the built-in words are only built the first time they're asked for
+    pub fn built_in(sorted: bool) -> Self {
+        let dictionary: Arc<dyn WordSource> = match sorted {
+            true => Arc::new(SortedWords::new(utils::dictionary().iter().cloned())),
+            false => utils::dictionary(),
+        };
+        WordLists {
+            dictionary,
+            answers: utils::answers(),
+            path: None,
+            skipped: 0,
+        }
+    }
+
+    pub fn load(path: &Path, length: usize, sorted: bool) -> Result<Self, GameError> {
+        let list = utils::load_dictionary(path, length)?;
+        let words: Arc<dyn WordSource> = match sorted {
+            true => Arc::new(SortedWords::new(list.words)),
+            false => Arc::new(list.words),
+        };
+        Ok(WordLists {
+            dictionary: words.clone(),
+            answers: words,
+            path: Some(path.to_path_buf()),
+            skipped: list.skipped,
+        })
+    }
+}
+
+impl WordSource for WordLists {
+    fn contains(&self, word: &str) -> bool {
+        self.dictionary.contains(word)
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        self.dictionary.words()
+    }
+
+    fn answers(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        self.answers.words()
+    }
+}
+
+// the words in order, without repeats, and found by binary search
+pub struct SortedWords(Vec<String>);
+
+impl SortedWords {
+    pub fn new(words: impl IntoIterator<Item = String>) -> Self {
+        let mut words: Vec<String> = words.into_iter().collect();
+        words.sort_unstable();
+        words.dedup();
+        words.shrink_to_fit();
+        SortedWords(words)
+    }
+}
+
+impl WordSource for SortedWords {
+    fn contains(&self, word: &str) -> bool {
+        self.0
+            .binary_search_by(|candidate| candidate.as_str().cmp(word))
+            .is_ok()
+    }
+
+    fn words(&self) -> Box<dyn Iterator<Item = &String> + '_> {
+        Box::new(self.0.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::utils;
+
+    #[test]
+    fn test_both_word_sources_agree_on_every_word() {
+        let set = utils::dictionary();
+        let sorted = SortedWords::new(set.iter().cloned());
+
+        for word in set.iter().chain(utils::answers().iter()) {
+            assert!(WordSource::contains(&set, word));
+            assert!(
+                sorted.contains(word),
+                "{word} is missing from the sorted words"
+            );
+        }
+        for word in ["", "zzzzz", "slum", "slumps", "SLUMP", "aaaaa", "zzzzzz"] {
+            assert_eq!(WordSource::contains(&set, word), sorted.contains(word));
+        }
+        assert_eq!(sorted.words().count(), set.words().count());
+    }
+
+    #[test]
+    fn test_sorted_words_are_in_order_without_repeats() {
+        let sorted = SortedWords::new(["plump", "clump", "plump", "acorn"].map(String::from));
+        let words: Vec<&String> = sorted.words().collect();
+        assert_eq!(words, ["acorn", "clump", "plump"]);
+        assert!(sorted.contains("acorn") && sorted.contains("plump"));
+        assert!(!sorted.contains("slump"));
+    }
+}
@@ -2323,3 +2323,488 @@ pub fn dictionary_words() -> Vec<String> {
         "zonal".to_string(),
     ]
 }
+
+// the answers are picked from this shorter list of everyday words, so the player isn't asked to
+// find something obscure. Every one of them is also in the dictionary above
+pub fn answer_words() -> Vec<String> {
+    vec![
+        "about".to_string(),
+        "above".to_string(),
+        "abuse".to_string(),
+        "actor".to_string(),
+        "acute".to_string(),
+        "admit".to_string(),
+        "adopt".to_string(),
+        "adult".to_string(),
+        "after".to_string(),
+        "again".to_string(),
+        "agent".to_string(),
+        "agree".to_string(),
+        "ahead".to_string(),
+        "alarm".to_string(),
+        "album".to_string(),
+        "alert".to_string(),
+        "alike".to_string(),
+        "alive".to_string(),
+        "allow".to_string(),
+        "alone".to_string(),
+        "along".to_string(),
+        "alter".to_string(),
+        "among".to_string(),
+        "anger".to_string(),
+        "angle".to_string(),
+        "angry".to_string(),
+        "apart".to_string(),
+        "apple".to_string(),
+        "apply".to_string(),
+        "arena".to_string(),
+        "argue".to_string(),
+        "arise".to_string(),
+        "array".to_string(),
+        "aside".to_string(),
+        "asset".to_string(),
+        "audio".to_string(),
+        "audit".to_string(),
+        "avoid".to_string(),
+        "award".to_string(),
+        "aware".to_string(),
+        "badly".to_string(),
+        "baker".to_string(),
+        "basic".to_string(),
+        "basis".to_string(),
+        "beach".to_string(),
+        "began".to_string(),
+        "begin".to_string(),
+        "begun".to_string(),
+        "being".to_string(),
+        "below".to_string(),
+        "bench".to_string(),
+        "billy".to_string(),
+        "birth".to_string(),
+        "black".to_string(),
+        "blame".to_string(),
+        "blind".to_string(),
+        "block".to_string(),
+        "blood".to_string(),
+        "board".to_string(),
+        "boost".to_string(),
+        "booth".to_string(),
+        "bound".to_string(),
+        "brain".to_string(),
+        "brand".to_string(),
+        "bread".to_string(),
+        "break".to_string(),
+        "breed".to_string(),
+        "brief".to_string(),
+        "bring".to_string(),
+        "broad".to_string(),
+        "broke".to_string(),
+        "brown".to_string(),
+        "build".to_string(),
+        "built".to_string(),
+        "buyer".to_string(),
+        "cable".to_string(),
+        "carry".to_string(),
+        "catch".to_string(),
+        "cause".to_string(),
+        "chain".to_string(),
+        "chair".to_string(),
+        "chart".to_string(),
+        "chase".to_string(),
+        "cheap".to_string(),
+        "check".to_string(),
+        "chest".to_string(),
+        "chief".to_string(),
+        "child".to_string(),
+        "china".to_string(),
+        "chose".to_string(),
+        "civil".to_string(),
+        "claim".to_string(),
+        "class".to_string(),
+        "clean".to_string(),
+        "clear".to_string(),
+        "click".to_string(),
+        "clock".to_string(),
+        "close".to_string(),
+        "coach".to_string(),
+        "coast".to_string(),
+        "could".to_string(),
+        "count".to_string(),
+        "court".to_string(),
+        "cover".to_string(),
+        "craft".to_string(),
+        "crane".to_string(),
+        "crash".to_string(),
+        "cream".to_string(),
+        "crime".to_string(),
+        "cross".to_string(),
+        "crowd".to_string(),
+        "crown".to_string(),
+        "curve".to_string(),
+        "cycle".to_string(),
+        "daily".to_string(),
+        "dance".to_string(),
+        "dealt".to_string(),
+        "death".to_string(),
+        "debut".to_string(),
+        "delay".to_string(),
+        "depth".to_string(),
+        "doing".to_string(),
+        "doubt".to_string(),
+        "dozen".to_string(),
+        "draft".to_string(),
+        "drama".to_string(),
+        "drawn".to_string(),
+        "dream".to_string(),
+        "dress".to_string(),
+        "drill".to_string(),
+        "drink".to_string(),
+        "drive".to_string(),
+        "drove".to_string(),
+        "dying".to_string(),
+        "eager".to_string(),
+        "early".to_string(),
+        "earth".to_string(),
+        "eight".to_string(),
+        "elite".to_string(),
+        "empty".to_string(),
+        "enemy".to_string(),
+        "enjoy".to_string(),
+        "enter".to_string(),
+        "entry".to_string(),
+        "equal".to_string(),
+        "error".to_string(),
+        "event".to_string(),
+        "every".to_string(),
+        "exact".to_string(),
+        "exist".to_string(),
+        "extra".to_string(),
+        "faith".to_string(),
+        "false".to_string(),
+        "fault".to_string(),
+        "fiber".to_string(),
+        "field".to_string(),
+        "fifth".to_string(),
+        "fifty".to_string(),
+        "fight".to_string(),
+        "final".to_string(),
+        "first".to_string(),
+        "flash".to_string(),
+        "fleet".to_string(),
+        "floor".to_string(),
+        "fluid".to_string(),
+        "focus".to_string(),
+        "force".to_string(),
+        "forth".to_string(),
+        "forty".to_string(),
+        "forum".to_string(),
+        "found".to_string(),
+        "frame".to_string(),
+        "frank".to_string(),
+        "fraud".to_string(),
+        "fresh".to_string(),
+        "front".to_string(),
+        "fruit".to_string(),
+        "fully".to_string(),
+        "funny".to_string(),
+        "giant".to_string(),
+        "given".to_string(),
+        "glass".to_string(),
+        "globe".to_string(),
+        "going".to_string(),
+        "grace".to_string(),
+        "grade".to_string(),
+        "grand".to_string(),
+        "grant".to_string(),
+        "grass".to_string(),
+        "great".to_string(),
+        "green".to_string(),
+        "gross".to_string(),
+        "group".to_string(),
+        "grown".to_string(),
+        "guard".to_string(),
+        "guess".to_string(),
+        "guest".to_string(),
+        "guide".to_string(),
+        "happy".to_string(),
+        "harry".to_string(),
+        "heart".to_string(),
+        "heavy".to_string(),
+        "hence".to_string(),
+        "horse".to_string(),
+        "hotel".to_string(),
+        "house".to_string(),
+        "human".to_string(),
+        "ideal".to_string(),
+        "image".to_string(),
+        "index".to_string(),
+        "inner".to_string(),
+        "input".to_string(),
+        "issue".to_string(),
+        "joint".to_string(),
+        "judge".to_string(),
+        "known".to_string(),
+        "label".to_string(),
+        "large".to_string(),
+        "later".to_string(),
+        "laugh".to_string(),
+        "layer".to_string(),
+        "learn".to_string(),
+        "lease".to_string(),
+        "least".to_string(),
+        "leave".to_string(),
+        "legal".to_string(),
+        "level".to_string(),
+        "light".to_string(),
+        "limit".to_string(),
+        "local".to_string(),
+        "logic".to_string(),
+        "loose".to_string(),
+        "lower".to_string(),
+        "lucky".to_string(),
+        "lunch".to_string(),
+        "lying".to_string(),
+        "magic".to_string(),
+        "major".to_string(),
+        "maker".to_string(),
+        "march".to_string(),
+        "match".to_string(),
+        "maybe".to_string(),
+        "mayor".to_string(),
+        "meant".to_string(),
+        "media".to_string(),
+        "metal".to_string(),
+        "might".to_string(),
+        "minor".to_string(),
+        "minus".to_string(),
+        "model".to_string(),
+        "money".to_string(),
+        "month".to_string(),
+        "moral".to_string(),
+        "motor".to_string(),
+        "mount".to_string(),
+        "mouse".to_string(),
+        "mouth".to_string(),
+        "movie".to_string(),
+        "music".to_string(),
+        "never".to_string(),
+        "newly".to_string(),
+        "night".to_string(),
+        "noise".to_string(),
+        "north".to_string(),
+        "novel".to_string(),
+        "nurse".to_string(),
+        "occur".to_string(),
+        "ocean".to_string(),
+        "offer".to_string(),
+        "often".to_string(),
+        "order".to_string(),
+        "other".to_string(),
+        "ought".to_string(),
+        "paint".to_string(),
+        "panel".to_string(),
+        "paper".to_string(),
+        "party".to_string(),
+        "peace".to_string(),
+        "phase".to_string(),
+        "phone".to_string(),
+        "photo".to_string(),
+        "piece".to_string(),
+        "pilot".to_string(),
+        "pitch".to_string(),
+        "place".to_string(),
+        "plain".to_string(),
+        "plane".to_string(),
+        "plant".to_string(),
+        "plate".to_string(),
+        "point".to_string(),
+        "pound".to_string(),
+        "power".to_string(),
+        "press".to_string(),
+        "price".to_string(),
+        "pride".to_string(),
+        "prime".to_string(),
+        "print".to_string(),
+        "prior".to_string(),
+        "prize".to_string(),
+        "proof".to_string(),
+        "proud".to_string(),
+        "prove".to_string(),
+        "queen".to_string(),
+        "quick".to_string(),
+        "quiet".to_string(),
+        "quite".to_string(),
+        "radio".to_string(),
+        "raise".to_string(),
+        "range".to_string(),
+        "rapid".to_string(),
+        "ratio".to_string(),
+        "reach".to_string(),
+        "ready".to_string(),
+        "refer".to_string(),
+        "right".to_string(),
+        "rival".to_string(),
+        "river".to_string(),
+        "robin".to_string(),
+        "roger".to_string(),
+        "rough".to_string(),
+        "round".to_string(),
+        "route".to_string(),
+        "royal".to_string(),
+        "rural".to_string(),
+        "scale".to_string(),
+        "scene".to_string(),
+        "scope".to_string(),
+        "score".to_string(),
+        "sense".to_string(),
+        "serve".to_string(),
+        "seven".to_string(),
+        "shall".to_string(),
+        "shape".to_string(),
+        "share".to_string(),
+        "sharp".to_string(),
+        "sheet".to_string(),
+        "shelf".to_string(),
+        "shell".to_string(),
+        "shift".to_string(),
+        "shirt".to_string(),
+        "shock".to_string(),
+        "shoot".to_string(),
+        "short".to_string(),
+        "shown".to_string(),
+        "sight".to_string(),
+        "since".to_string(),
+        "sixth".to_string(),
+        "sixty".to_string(),
+        "skill".to_string(),
+        "sleep".to_string(),
+        "slide".to_string(),
+        "slump".to_string(),
+        "small".to_string(),
+        "smart".to_string(),
+        "smile".to_string(),
+        "smith".to_string(),
+        "smoke".to_string(),
+        "solid".to_string(),
+        "solve".to_string(),
+        "sorry".to_string(),
+        "sound".to_string(),
+        "south".to_string(),
+        "space".to_string(),
+        "spare".to_string(),
+        "speak".to_string(),
+        "speed".to_string(),
+        "spend".to_string(),
+        "spent".to_string(),
+        "split".to_string(),
+        "spoke".to_string(),
+        "sport".to_string(),
+        "staff".to_string(),
+        "stage".to_string(),
+        "stake".to_string(),
+        "stand".to_string(),
+        "stare".to_string(),
+        "start".to_string(),
+        "state".to_string(),
+        "steam".to_string(),
+        "steel".to_string(),
+        "stick".to_string(),
+        "still".to_string(),
+        "stock".to_string(),
+        "stone".to_string(),
+        "stood".to_string(),
+        "store".to_string(),
+        "storm".to_string(),
+        "story".to_string(),
+        "strip".to_string(),
+        "stuck".to_string(),
+        "study".to_string(),
+        "stuff".to_string(),
+        "style".to_string(),
+        "sugar".to_string(),
+        "suite".to_string(),
+        "super".to_string(),
+        "sweet".to_string(),
+        "table".to_string(),
+        "taken".to_string(),
+        "taste".to_string(),
+        "teach".to_string(),
+        "teeth".to_string(),
+        "thank".to_string(),
+        "theft".to_string(),
+        "their".to_string(),
+        "theme".to_string(),
+        "there".to_string(),
+        "these".to_string(),
+        "thick".to_string(),
+        "thing".to_string(),
+        "think".to_string(),
+        "third".to_string(),
+        "those".to_string(),
+        "three".to_string(),
+        "threw".to_string(),
+        "throw".to_string(),
+        "tight".to_string(),
+        "title".to_string(),
+        "today".to_string(),
+        "topic".to_string(),
+        "total".to_string(),
+        "touch".to_string(),
+        "tough".to_string(),
+        "tower".to_string(),
+        "track".to_string(),
+        "trade".to_string(),
+        "train".to_string(),
+        "treat".to_string(),
+        "trend".to_string(),
+        "trial".to_string(),
+        "tried".to_string(),
+        "truck".to_string(),
+        "truly".to_string(),
+        "trust".to_string(),
+        "truth".to_string(),
+        "twice".to_string(),
+        "under".to_string(),
+        "undue".to_string(),
+        "union".to_string(),
+        "unity".to_string(),
+        "until".to_string(),
+        "upper".to_string(),
+        "upset".to_string(),
+        "urban".to_string(),
+        "usage".to_string(),
+        "usual".to_string(),
+        "valid".to_string(),
+        "value".to_string(),
+        "video".to_string(),
+        "virus".to_string(),
+        "visit".to_string(),
+        "vital".to_string(),
+        "voice".to_string(),
+        "waste".to_string(),
+        "watch".to_string(),
+        "water".to_string(),
+        "wheel".to_string(),
+        "where".to_string(),
+        "which".to_string(),
+        "while".to_string(),
+        "white".to_string(),
+        "whole".to_string(),
+        "whose".to_string(),
+        "woman".to_string(),
+        "women".to_string(),
+        "world".to_string(),
+        "worry".to_string(),
+        "worse".to_string(),
+        "worst".to_string(),
+        "worth".to_string(),
+        "would".to_string(),
+        "wound".to_string(),
+        "write".to_string(),
+        "wrong".to_string(),
+        "wrote".to_string(),
+        "yield".to_string(),
+        "young".to_string(),
+        "youth".to_string(),
+    ]
+}
@@ -1,13 +1,30 @@
-use crossterm::event::{self, Event as CEvent, KeyEvent};
+use crossterm::event::{self, Event as CEvent, KeyEvent, MouseButton, MouseEventKind};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+#[derive(Debug, PartialEq)]
 pub enum AppEvent<I> {
     Input(I),
+    // the new width and height of the terminal
+    Resize(u16, u16),
+    // the column and row of a left click
+    Click(u16, u16),
     Tick,
 }
 
+// the crossterm events we care about. The only mouse events kept are left clicks
+fn from_crossterm(event: CEvent) -> Option<AppEvent<KeyEvent>> {
+    match event {
+        CEvent::Key(key) => Some(AppEvent::Input(key)),
+        CEvent::Resize(width, height) => Some(AppEvent::Resize(width, height)),
+        CEvent::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+            Some(AppEvent::Click(mouse.column, mouse.row))
+        }
+        _ => None,
+    }
+}
+
 pub struct Events {
     rx: mpsc::Receiver<AppEvent<KeyEvent>>,
 }
@@ -28,23 +45,68 @@ impl Events {
                     .unwrap_or_else(|| Duration::from_secs(0));
 
                 if event::poll(timeout).expect("poll works") {
-                    if let CEvent::Key(key) = event::read().expect("can read events") {
-                        tx.send(AppEvent::Input(key)).expect("can send events");
+                    if let Some(event) = from_crossterm(event::read().expect("can read events")) {
+                        tx.send(event).expect("can send events");
                     }
                 }
 
-                if last_tick.elapsed() >= tick_rate {
-                    if let Ok(_) = tx.send(AppEvent::Tick) {
-                        last_tick = Instant::now();
-                    }
+                if last_tick.elapsed() >= tick_rate && tx.send(AppEvent::Tick).is_ok() {
+                    last_tick = Instant::now();
                 }
             }
         });
 
-        Events { rx: rx }
+        Events { rx }
     }
 
     pub fn next(&self) -> Result<AppEvent<KeyEvent>, mpsc::RecvError> {
         self.rx.recv()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use crossterm::event::{KeyCode, MouseEvent, MouseEventKind};
+
+    #[test]
+    fn test_keys_and_resizes_are_forwarded() {
+        let key = KeyEvent::from(KeyCode::Char('a'));
+        assert_eq!(from_crossterm(CEvent::Key(key)), Some(AppEvent::Input(key)));
+        assert_eq!(
+            from_crossterm(CEvent::Resize(80, 40)),
+            Some(AppEvent::Resize(80, 40))
+        );
+    }
+
+    fn mouse(kind: MouseEventKind) -> CEvent {
+        CEvent::Mouse(MouseEvent {
+            kind,
+            column: 12,
+            row: 3,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn test_left_clicks_are_forwarded() {
+        assert_eq!(
+            from_crossterm(mouse(MouseEventKind::Down(MouseButton::Left))),
+            Some(AppEvent::Click(12, 3))
+        );
+    }
+
+    #[test]
+    fn test_other_mouse_events_are_dropped() {
+        assert_eq!(from_crossterm(mouse(MouseEventKind::Moved)), None);
+        assert_eq!(
+            from_crossterm(mouse(MouseEventKind::Down(MouseButton::Right))),
+            None
+        );
+        assert_eq!(
+            from_crossterm(mouse(MouseEventKind::Up(MouseButton::Left))),
+            None
+        );
+    }
+}
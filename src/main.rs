@@ -1,19 +1,30 @@
 mod app;
+mod batch;
+mod config;
 mod engine;
 mod events;
 mod theme;
+mod tutorial;
 mod ui;
 
-use crate::app::{App, AppOptions};
-use crate::engine::{GameDifficulty, GameOptions};
+use crate::app::{App, AppOptions, AssistLevel, KeyboardLayout};
+use crate::config::{config_dir, Config};
+use crate::engine::{parse_share_grid, Game, GameDifficulty, GameError, GameOptions, GameSnapshot};
 use crate::events::{AppEvent, Events};
-use crate::theme::Theme;
+use crate::theme::{Theme, ThemeName};
 
+use chrono::Local;
 use clap::Parser;
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
-use std::io;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, size};
+use std::error::Error;
+use std::io::{self, Write};
+use std::panic;
+use std::path::PathBuf;
 use std::time::Duration;
-use tui::{backend::CrosstermBackend, Terminal};
+use tui::backend::{Backend, CrosstermBackend};
+use tui::{layout::Rect, Terminal};
 
 #[derive(Parser, Debug)]
 #[clap(about = "Wordlet is a command line Wordle clone.", version, author)]
@@ -21,67 +32,905 @@ struct Args {
     #[clap(
         short,
         long,
-        default_value = "easy",
-        help = "Change the game's difficulty. Valid values are easy and hard"
+        arg_enum,
+        help = "Change the game's difficulty. Defaults to easy"
     )]
-    difficulty: String,
+    difficulty: Option<GameDifficulty>,
 
     #[clap(
         short,
         long,
-        default_value = "dark",
-        help = "Change the display colors. Valid values are light and dark"
+        arg_enum,
+        help = "Change the display colors. Defaults to dark"
     )]
-    theme: String,
+    theme: Option<ThemeName>,
+
+    #[clap(
+        long,
+        possible_values = &["qwerty", "azerty", "dvorak"],
+        help = "Arrange the on-screen keyboard like yours. Defaults to qwerty"
+    )]
+    keyboard: Option<String>,
+
+    #[clap(
+        long,
+        help = "Load the display colors from a file. Colors can be names like light_blue, or #rrggbb"
+    )]
+    theme_file: Option<PathBuf>,
+
+    #[clap(long, help = "Print the built-in themes and their colors, then exit")]
+    list_themes: bool,
+
+    #[clap(
+        long,
+        help = "Mark guessed letters with symbols as well as colors: + is in the right spot, ~ is in the word, x is not in the word"
+    )]
+    symbols: bool,
+
+    #[clap(
+        long,
+        help = "Play a guided practice game that explains the rules as you go"
+    )]
+    tutorial: bool,
+
+    #[clap(long, help = "Show the number of guesses left as dots in the header")]
+    guess_dots: bool,
+
+    #[clap(long, help = "Show what each color means under the keyboard")]
+    legend: bool,
+
+    #[clap(long, help = "How many letters the words have. Defaults to 5")]
+    word_length: Option<usize>,
+
+    // a challenge code has the number of guesses in it
+    #[clap(
+        long,
+        conflicts_with = "challenge",
+        help = "How many guesses you get. Defaults to 6"
+    )]
+    guesses: Option<usize>,
+
+    #[clap(
+        long,
+        help = "Play the puzzle from a challenge code a friend shared with you"
+    )]
+    challenge: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["challenge", "friend", "tutorial", "boards", "resume", "batch", "plain", "practice"],
+        help = "Watch a finished game from the replay code a friend shared with you"
+    )]
+    replay_code: Option<String>,
+
+    #[clap(
+        long,
+        help = "Press Enter to play again when a game ends, instead of exiting"
+    )]
+    replay: bool,
+
+    #[clap(
+        long,
+        help = "Guess the word as soon as its last letter is typed, without pressing Enter"
+    )]
+    auto_submit: bool,
+
+    #[clap(
+        long,
+        help = "Type the letters you've already found into every new row",
+        conflicts_with_all = &["boards"]
+    )]
+    assist: bool,
+
+    #[clap(
+        long,
+        help = "Press F2 to show the answer. Games where it was shown aren't counted in the stats"
+    )]
+    cheats: bool,
+
+    #[clap(long, help = "Start without the welcome message")]
+    no_welcome: bool,
+
+    // every one of these would give the same puzzle again, so there'd be nothing to practice
+    #[clap(
+        long,
+        conflicts_with_all = &["daily", "seed", "answer", "challenge", "friend", "tutorial"],
+        help = "Play one random puzzle after another. Each result is shown for a moment, then the next game starts. Esc stops"
+    )]
+    practice: bool,
+
+    #[clap(
+        long,
+        help = "Try to reproduce the colors of a friend's shared result. Separate the rows with spaces"
+    )]
+    friend: Option<String>,
+
+    #[clap(
+        long,
+        help = "Practice only: accept any word of the chosen length as a guess, even one that isn't in the dictionary. This makes hard mode trivial"
+    )]
+    no_dictionary_check: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "boards", "resume", "batch", "plain", "replay-code"],
+        help = "Print the words that fit a pattern like s..te, where a dot is any letter, then exit"
+    )]
+    find: Option<String>,
+
+    #[clap(
+        long,
+        requires = "find",
+        help = "With --find, only print words that have all of these letters"
+    )]
+    containing: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "resume", "replay-code"],
+        help = "Start with these guesses already on the board, separated by spaces, to set up a puzzle"
+    )]
+    start_with: Option<String>,
+
+    #[clap(long, help = "Let a word be guessed again. It still uses up a guess")]
+    allow_duplicates: bool,
+
+    #[clap(
+        long,
+        default_value = "true",
+        parse(try_from_str),
+        possible_values = &["true", "false"],
+        help = "Use --reveal-on-loss=false to keep the answer secret when a game is lost, so others can still play it"
+    )]
+    reveal_on_loss: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["challenge", "tutorial"],
+        help = "Play with your own words instead of the built-in ones. The file should have one word per line"
+    )]
+    dictionary: Option<PathBuf>,
+
+    #[clap(
+        long,
+        requires = "dictionary",
+        help = "Keep the --dictionary words in a sorted list, which takes less memory for a very long list"
+    )]
+    sorted_dictionary: bool,
+
+    #[clap(
+        long,
+        help = "Offer a hint when you spend this many seconds on a guess"
+    )]
+    hint_after: Option<u32>,
+
+    #[clap(
+        long,
+        possible_values = &["0", "1", "2"],
+        help = "How much help is offered: 0 for none, 1 for hints about letters, 2 for suggested words too. Defaults to 2"
+    )]
+    assist_level: Option<u8>,
+
+    #[clap(
+        long,
+        default_value = "100",
+        parse(try_from_str = parse_tick_ms),
+        help = "How often the screen is redrawn, in milliseconds. Animations and timers move once per tick"
+    )]
+    tick_ms: u64,
+
+    #[clap(long, help = "Pick the same answer every time for the same number")]
+    seed: Option<u64>,
+
+    #[clap(long, help = "Play today's puzzle, which is the same for everyone")]
+    daily: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "challenge", "friend"],
+        help = "Solve this many words at once with the same guesses. Every extra board adds a guess. Defaults to 1"
+    )]
+    boards: Option<usize>,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "boards"],
+        help = "Carry on with the game you quit last time, if there is one"
+    )]
+    resume: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "boards", "resume"],
+        help = "Play without the board: read one guess per line from stdin and print each result"
+    )]
+    batch: bool,
+
+    #[clap(
+        long,
+        conflicts_with_all = &["tutorial", "boards", "resume", "batch"],
+        help = "Play without the board, for screen readers: read one guess per line and describe every letter in words"
+    )]
+    plain: bool,
+
+    #[clap(
+        long,
+        requires = "batch",
+        possible_values = &["text", "json"],
+        help = "How --batch prints each result. Defaults to text"
+    )]
+    format: Option<String>,
+
+    // for reproducing bug reports. It's hidden so that it doesn't spoil the game
+    #[clap(long, hide = true)]
+    answer: Option<String>,
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    enable_raw_mode()?;
+// how long a practice game's result stays up before the next game starts
+const PRACTICE_RESULT_SECONDS: u32 = 2;
+
+// how long the welcome message stays up if no word is played
+const WELCOME_SECONDS: u32 = 5;
+
+// the boards sit side by side, so past a handful there's no terminal wide enough anyway
+const MAX_BOARDS: usize = 8;
+
+// every absurd board would dodge the same way, so they'd all be the same board
+fn board_count(args: &Args, difficulty: GameDifficulty) -> Result<usize, GameError> {
+    match args.boards.unwrap_or(1) {
+        boards @ 2.. if difficulty == GameDifficulty::Absurd => {
+            Err(GameError::InvalidBoardCount(boards))
+        }
+        boards @ 1..=MAX_BOARDS => Ok(boards),
+        boards => Err(GameError::InvalidBoardCount(boards)),
+    }
+}
+
+// a tick of zero would redraw in a busy loop
+fn parse_tick_ms(value: &str) -> Result<u64, String> {
+    match value.parse::<u64>() {
+        Ok(0) => Err(String::from("the tick has to be at least 1 millisecond")),
+        Ok(ms) => Ok(ms),
+        Err(e) => Err(e.to_string()),
+    }
+}
 
+fn assist_level(args: &Args) -> AssistLevel {
+    match args.assist_level {
+        Some(0) => AssistLevel::Off,
+        Some(1) => AssistLevel::Hints,
+        _ => AssistLevel::Candidates,
+    }
+}
+
+fn keyboard_layout(args: &Args) -> KeyboardLayout {
+    match args.keyboard.as_deref() {
+        Some("azerty") => KeyboardLayout::Azerty,
+        Some("dvorak") => KeyboardLayout::Dvorak,
+        _ => KeyboardLayout::Qwerty,
+    }
+}
+
+// works like --answer, for demos and scripts that shouldn't show the answer on the command line
+const ANSWER_VARIABLE: &str = "WORDLET_ANSWER";
+
+// The flag wins over the variable, and so does anything else that picks the answer, so a
+// variable left set in the shell can't quietly turn every --daily game into the same word
+fn forced_answer(args: &Args, from_variable: Option<String>) -> Option<String> {
+    let from_variable = from_variable
+        .map(|answer| answer.trim().to_string())
+        .filter(|answer| !answer.is_empty())
+        .filter(|_| !args.daily && args.seed.is_none() && !args.practice);
+    args.answer
+        .clone()
+        .or(from_variable)
+        .map(|answer| answer.to_lowercase())
+}
+
+// everything the game is set up with comes from the player, so it's all checked here before the
+// terminal is taken over
+fn game_options(args: &Args, config: &Config) -> Result<GameOptions, GameError> {
+    let difficulty = args.difficulty.unwrap_or(config.difficulty);
+
+    let game_config = match &args.challenge {
+        Some(code) => GameOptions::from_challenge_code(code)?,
+        None => GameOptions {
+            answer: forced_answer(args, std::env::var(ANSWER_VARIABLE).ok()),
+            difficulty,
+            word_length: args.word_length.unwrap_or(config.word_length),
+            max_guesses: args.guesses.unwrap_or(config.guesses),
+            skip_dictionary_check: args.no_dictionary_check,
+            allow_duplicates: args.allow_duplicates,
+            reveal_on_loss: args.reveal_on_loss,
+            dictionary_path: args.dictionary.clone(),
+            sorted_dictionary: args.sorted_dictionary,
+            seed: args.seed,
+            daily: args.daily.then(|| Local::today().naive_local()),
+            initial_guesses: args
+                .start_with
+                .iter()
+                .flat_map(|words| words.split_whitespace())
+                .map(str::to_lowercase)
+                .collect(),
+            ..Default::default()
+        },
+    };
+    let words = game_config.validate()?;
+
+    if let (Some(path), skipped @ 1..) = (&words.path, words.skipped) {
+        let lines = if skipped == 1 { "line" } else { "lines" };
+        eprintln!(
+            "Skipped {skipped} {lines} of {} that aren't {} letter words.",
+            path.display(),
+            game_config.word_length
+        );
+    }
+    // every game from here on plays with the words that were just read
+    Ok(GameOptions {
+        words: Some(words),
+        ..game_config
+    })
+}
+
+// Errors are printed with their message rather than their Debug form, and only once the
+// terminal is back to normal, so they can be read
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("Error: {error}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
-    let difficulty = match args.difficulty.as_ref() {
-        "hard" => GameDifficulty::Hard,
-        _ => GameDifficulty::Easy,
+    // before the config is read, so a broken config file doesn't stop the list
+    if args.list_themes {
+        for (name, theme) in Theme::built_in() {
+            println!("{name:<10} {}", theme.summary());
+        }
+        return Ok(());
+    }
+
+    let config = match config_dir() {
+        Some(dir) => Config::load(&dir.join("config.toml"))?,
+        None => Config::default(),
+    };
+
+    let mut theme = match &args.theme_file {
+        Some(path) => Theme::from_file(path)?,
+        None => args.theme.unwrap_or(config.theme).theme(),
+    };
+    theme.use_symbols |= args.symbols;
+
+    let game_config = game_options(&args, &config)?;
+    let boards = board_count(&args, game_config.difficulty)?;
+
+    if let Some(pattern) = &args.find {
+        let letters = args.containing.as_deref().unwrap_or("");
+        for word in Game::new(game_config)?.find_words(pattern, letters)? {
+            println!("{word}");
+        }
+        return Ok(());
+    }
+
+    if args.batch || args.plain {
+        let format = match (args.plain, args.format.as_deref()) {
+            (true, _) => batch::Format::Plain,
+            (false, Some("json")) => batch::Format::Json,
+            (false, _) => batch::Format::Text,
+        };
+        batch::run(
+            Game::new(game_config)?,
+            format,
+            io::stdin().lock(),
+            io::stdout().lock(),
+        )?;
+        return Ok(());
+    }
+
+    let friend_grid = match &args.friend {
+        Some(grid) => Some(parse_share_grid(grid)?),
+        None => None,
     };
 
-    let theme = match args.theme.as_ref() {
-        "light" => Theme::light_theme(),
-        _ => Theme::dark_theme(),
+    // the options above only apply to a new game. A saved one that can't be read is forgotten,
+    // and we start fresh
+    let saved_game_path = config_dir().map(|dir| dir.join("saved_game.json"));
+    let resumed_game = match (&saved_game_path, args.resume) {
+        (Some(path), true) => GameSnapshot::load(path).and_then(|s| Game::restore(&s).ok()),
+        _ => None,
+    };
+    // a replayed game is opened like a saved one. It's already over, so it's never saved
+    let resumed_game = match &args.replay_code {
+        Some(code) => Some(Game::from_replay(code)?),
+        None => resumed_game,
     };
 
+    let tick_rate = Duration::from_millis(args.tick_ms);
+    // a tick longer than a second still counts as one, so waits never round down to nothing
+    let ticks_per_second = (1000 / args.tick_ms).max(1) as u32;
+
     let mut app = App::new(AppOptions {
-        theme: theme,
-        game_config: GameOptions {
-            answer: None,
-            difficulty: difficulty,
-        },
-    });
+        theme,
+        game_config,
+        tutorial: args.tutorial,
+        boards,
+        show_guess_dots: args.guess_dots,
+        show_legend: args.legend,
+        keyboard_rows: keyboard_layout(&args).rows(),
+        restart_on_enter: args.replay,
+        auto_submit: args.auto_submit,
+        assist: args.assist,
+        cheats: args.cheats,
+        friend_grid,
+        idle_hint_ticks: args
+            .hint_after
+            .map(|seconds| seconds.max(1) * ticks_per_second),
+        assist_level: assist_level(&args),
+        auto_restart_ticks: args
+            .practice
+            .then_some(PRACTICE_RESULT_SECONDS * ticks_per_second),
+        show_welcome: !args.no_welcome,
+        welcome_ticks: Some(WELCOME_SECONDS * ticks_per_second),
+        stats_path: config_dir().map(|dir| dir.join("stats.json")),
+        saved_game_path,
+        resumed_game,
+    })?;
 
-    let tick_rate = Duration::from_millis(100);
     let events = Events::new(tick_rate);
 
-    let stdout = io::stdout();
+    let (width, height) = size()?;
+    let (columns, rows) = (app.game().word_length(), app.session.max_guesses());
+    if !ui::fits_in_terminal(boards, columns, rows, width, height) {
+        let (min_width, min_height) = ui::minimum_terminal_size(boards, columns, rows);
+        eprintln!(
+            "Your terminal is {width}x{height}, but Wordlet needs at least {min_width}x{min_height}. Please make the window bigger and try again."
+        );
+        std::process::exit(1);
+    }
+
+    enable_raw_mode()?;
+    restore_terminal_on_panic();
+    // from here on the terminal is put back however run returns, so an error doesn't leave it in
+    // raw mode
+    let restore = RestoreTerminal;
+
+    let mut stdout = io::stdout();
+    // so the keyboard can be clicked
+    execute!(stdout, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
 
+    let played = play(&mut terminal, &mut app, &events);
+    drop(restore);
+    played?;
+
+    for game in app.session.boards() {
+        if let Some(summary) = game.summary() {
+            println!(
+                "{} guesses: {} in the right spot, {} in the wrong spot, {} not in the word.",
+                summary.guesses_used, summary.in_right_place, summary.in_word, summary.not_in_word
+            );
+        }
+    }
+
+    // the share grid and the challenge code are both for a single word
+    if let [game] = app.session.boards() {
+        if let Some(text) = game.share_text() {
+            println!("\n{text}\n");
+        }
+
+        if let Some(code) = game.challenge_code() {
+            println!("Challenge a friend to this puzzle with: wordlet --challenge {code}");
+        }
+
+        if let Some(code) = game.to_replay() {
+            println!("Show a friend how you played it with: wordlet --replay-code {code}");
+        }
+    }
+
+    let stats = &app.stats;
+    if stats.games_played > 0 {
+        println!(
+            "Played {}, won {}%, current streak {}, max streak {}. {} played with hints.",
+            stats.games_played,
+            stats.win_percentage(),
+            stats.current_streak,
+            stats.max_streak,
+            stats.assisted
+        );
+        let distribution: Vec<String> = stats
+            .distribution
+            .iter()
+            .enumerate()
+            .map(|(i, wins)| format!("{}: {wins}", i + 1))
+            .collect();
+        println!("Wins by number of guesses: {}", distribution.join(", "));
+        if let Some(best) = stats.best_time {
+            println!("Fastest win: {}", ui::format_duration(best));
+        }
+    }
+
+    Ok(())
+}
+
+fn play(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    events: &Events,
+) -> Result<(), Box<dyn Error>> {
     loop {
         terminal.draw(|frame| {
-            let _r = ui::draw(frame, &mut app);
+            let _r = ui::draw(frame, app);
         })?;
 
         match events.next()? {
             AppEvent::Input(event) => app.on_key(event),
-            AppEvent::Tick => {}
+            // the board is laid out again on every draw, so it re-centers at the top of the loop
+            AppEvent::Resize(width, height) => terminal.resize(Rect::new(0, 0, width, height))?,
+            AppEvent::Click(column, row) => app.on_click(column, row),
+            AppEvent::Tick => app.on_tick(),
         }
 
         if app.should_quit {
-            disable_raw_mode()?;
-            terminal.clear()?;
-            terminal.show_cursor()?;
-            break;
+            return Ok(());
         }
     }
+}
 
+// undoes everything run does to the terminal before the game starts
+fn restore_terminal<B: Backend + Write>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>> {
+    execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    disable_raw_mode()?;
+    terminal.clear()?;
+    terminal.show_cursor()?;
     Ok(())
 }
+
+// The terminal the game was using isn't always reachable when it has to be put back, but a new
+// one on the same stdout does it just as well. Raw mode goes first, in case that can't be made.
+fn restore_stdout() {
+    let _ = disable_raw_mode();
+    if let Ok(mut terminal) = Terminal::new(CrosstermBackend::new(io::stdout())) {
+        let _ = restore_terminal(&mut terminal);
+    }
+}
+
+// puts the terminal back when it goes out of scope, whether run finished or returned an error
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        restore_stdout();
+    }
+}
+
+// A panic mid-game would leave the shell in raw mode with no cursor, and the panic message
+// would be garbled.
+fn restore_terminal_on_panic() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_stdout();
+        default_hook(info);
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GuessResult;
+    use clap::ArgEnum;
+
+    fn options_for(args: &[&str]) -> Result<GameOptions, GameError> {
+        let args = Args::parse_from(std::iter::once("wordlet").chain(args.iter().copied()));
+        game_options(&args, &Config::default())
+    }
+
+    #[test]
+    fn test_a_made_up_answer_is_rejected() {
+        assert_eq!(
+            options_for(&["--answer", "djkle"]).err(),
+            Some(GameError::InvalidAnswer("djkle".to_string()))
+        );
+        assert_eq!(
+            options_for(&["--answer", "slump", "--word-length", "6"]).err(),
+            Some(GameError::InvalidWordLength(6))
+        );
+    }
+
+    #[test]
+    fn test_find_looks_through_the_dictionary_it_is_given() {
+        let path = std::env::temp_dir().join(format!("wordlet-find-{}.txt", std::process::id()));
+        std::fs::write(&path, "slate\nskate\nstone\nsmite\ncrane\nsuite\n").unwrap();
+        let dictionary = path.to_str().unwrap();
+        let find = |args: &[&str]| {
+            let args: Vec<&str> = ["--dictionary", dictionary]
+                .iter()
+                .chain(args)
+                .copied()
+                .collect();
+            Game::new(options_for(&args).unwrap()).unwrap()
+        };
+
+        let game = find(&["--find", "s..te"]);
+        assert_eq!(
+            game.find_words("s..te", "").unwrap(),
+            ["skate", "slate", "smite", "suite"]
+        );
+        assert_eq!(game.find_words("s..te", "a").unwrap(), ["skate", "slate"]);
+        assert_eq!(
+            game.find_words("s..t", "").err(),
+            Some(GameError::InvalidPattern(4, 5))
+        );
+
+        let args = Args::parse_from(["wordlet", "--find", "s..te", "--containing", "a"]);
+        assert_eq!(args.find.as_deref(), Some("s..te"));
+        assert_eq!(args.containing.as_deref(), Some("a"));
+        assert!(Args::try_parse_from(["wordlet", "--containing", "a"]).is_err());
+        assert!(Args::try_parse_from(["wordlet", "--find", "s..te", "--batch"]).is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_starting_guesses_are_checked_before_the_game_starts() {
+        let options = options_for(&["--answer", "slump", "--start-with", "Crane  slept"]).unwrap();
+        assert_eq!(options.initial_guesses, ["crane", "slept"]);
+
+        for (words, result) in [
+            ("crane djkle", GuessResult::NotInDictionary),
+            ("crane crane", GuessResult::DuplicateGuess),
+            ("crane slumps", GuessResult::IncorrectCharacterCount),
+            ("crane slump", GuessResult::GameIsAlreadyOver),
+        ] {
+            let bad_word = words.split(' ').nth(1).unwrap().to_string();
+            assert_eq!(
+                options_for(&["--answer", "slump", "--start-with", words]).err(),
+                Some(GameError::InvalidInitialGuess(bad_word, result))
+            );
+        }
+        assert!(Args::try_parse_from(["wordlet", "--start-with", "crane", "--resume"]).is_err());
+    }
+
+    #[test]
+    fn test_the_number_of_guesses_comes_from_the_challenge_code() {
+        let game = Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            max_guesses: 4,
+            ..Default::default()
+        })
+        .unwrap();
+        let code = game.challenge_code().unwrap();
+
+        assert_eq!(options_for(&["--challenge", &code]).unwrap().max_guesses, 4);
+        let error = Args::try_parse_from(["wordlet", "--challenge", &code, "--guesses", "8"]);
+        assert!(error.is_err());
+    }
+
+    #[test]
+    fn test_the_answer_is_revealed_after_a_loss_unless_turned_off() {
+        assert!(options_for(&[]).unwrap().reveal_on_loss);
+        assert!(
+            !options_for(&["--reveal-on-loss=false"])
+                .unwrap()
+                .reveal_on_loss
+        );
+        assert!(
+            options_for(&["--reveal-on-loss", "true"])
+                .unwrap()
+                .reveal_on_loss
+        );
+        assert!(Args::try_parse_from(["wordlet", "--reveal-on-loss=no"]).is_err());
+    }
+
+    #[test]
+    fn test_the_answer_can_come_from_the_environment() {
+        let args = Args::parse_from(["wordlet"]);
+        let answer = forced_answer(&args, Some(String::from(" SLUMP\n")));
+        assert_eq!(answer.as_deref(), Some("slump"));
+
+        let mut game = Game::new(GameOptions {
+            answer,
+            max_guesses: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        game.guess("crane");
+        assert_eq!(game.get_answer(), Ok(String::from("slump")));
+    }
+
+    #[test]
+    fn test_the_answer_flag_and_other_puzzles_win_over_the_environment() {
+        let answer = |args: &[&str], variable: &str| {
+            let args = Args::parse_from(std::iter::once("wordlet").chain(args.iter().copied()));
+            forced_answer(&args, Some(variable.to_string()))
+        };
+        assert_eq!(
+            answer(&["--answer", "crane"], "slump").as_deref(),
+            Some("crane")
+        );
+        assert_eq!(answer(&["--daily"], "slump"), None);
+        assert_eq!(answer(&["--seed", "4"], "slump"), None);
+        assert_eq!(answer(&["--practice"], "slump"), None);
+        assert_eq!(answer(&[], "  "), None);
+    }
+
+    #[test]
+    fn test_a_dictionary_that_cannot_be_read_is_an_error_not_a_panic() {
+        let error = options_for(&["--dictionary", "/no/such/dir/words.txt"])
+            .err()
+            .unwrap();
+        assert!(matches!(error, GameError::UnreadableDictionary(..)));
+        assert!(error.to_string().contains("/no/such/dir/words.txt"));
+    }
+
+    #[test]
+    fn test_flags_win_over_the_config_file() {
+        let config = Config {
+            difficulty: GameDifficulty::Hard,
+            word_length: 5,
+            guesses: 8,
+            ..Default::default()
+        };
+        let args = Args::parse_from(["wordlet", "--guesses", "4"]);
+        let options = game_options(&args, &config).unwrap();
+
+        assert_eq!(options.difficulty, GameDifficulty::Hard);
+        assert_eq!(options.max_guesses, 4);
+    }
+
+    #[test]
+    fn test_the_number_of_boards_is_checked() {
+        let boards = |args: &[&str]| {
+            board_count(
+                &Args::parse_from(std::iter::once("wordlet").chain(args.iter().copied())),
+                GameDifficulty::Easy,
+            )
+        };
+        assert_eq!(boards(&[]), Ok(1));
+        assert_eq!(boards(&["--boards", "4"]), Ok(4));
+        assert_eq!(
+            boards(&["--boards", "0"]),
+            Err(GameError::InvalidBoardCount(0))
+        );
+        assert_eq!(
+            boards(&["--boards", "9"]),
+            Err(GameError::InvalidBoardCount(9))
+        );
+        assert!(Args::try_parse_from(["wordlet", "--boards", "2", "--tutorial"]).is_err());
+
+        let absurd = Args::parse_from(["wordlet", "--boards", "2"]);
+        assert_eq!(
+            board_count(&absurd, GameDifficulty::Absurd),
+            Err(GameError::InvalidBoardCount(2))
+        );
+    }
+
+    // remembers what was done to it, since the test backend that comes with tui doesn't
+    #[derive(Default)]
+    struct RecordingBackend {
+        cleared: bool,
+        cursor_shown: bool,
+        written: Vec<u8>,
+    }
+
+    impl Write for RecordingBackend {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Backend for RecordingBackend {
+        fn draw<'a, I>(&mut self, _content: I) -> io::Result<()>
+        where
+            I: Iterator<Item = (u16, u16, &'a tui::buffer::Cell)>,
+        {
+            Ok(())
+        }
+        fn hide_cursor(&mut self) -> io::Result<()> {
+            self.cursor_shown = false;
+            Ok(())
+        }
+        fn show_cursor(&mut self) -> io::Result<()> {
+            self.cursor_shown = true;
+            Ok(())
+        }
+        fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+            Ok((0, 0))
+        }
+        fn set_cursor(&mut self, _x: u16, _y: u16) -> io::Result<()> {
+            Ok(())
+        }
+        fn clear(&mut self) -> io::Result<()> {
+            self.cleared = true;
+            Ok(())
+        }
+        fn size(&self) -> io::Result<Rect> {
+            Ok(Rect::new(0, 0, 10, 2))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_restoring_the_terminal_clears_it_and_shows_the_cursor() {
+        let mut terminal = Terminal::new(RecordingBackend::default()).unwrap();
+        terminal.hide_cursor().unwrap();
+
+        restore_terminal(&mut terminal).unwrap();
+        assert!(terminal.backend().cleared);
+        assert!(terminal.backend().cursor_shown);
+        // the escape code that stops the terminal sending mouse events
+        let written = String::from_utf8_lossy(&terminal.backend().written).to_string();
+        assert!(written.contains("\x1b[?1000l"));
+    }
+
+    #[test]
+    fn test_the_keyboard_layout_is_picked_by_name() {
+        let layout = |args: &[&str]| {
+            keyboard_layout(&Args::parse_from(
+                std::iter::once("wordlet").chain(args.iter().copied()),
+            ))
+        };
+        assert_eq!(layout(&[]), KeyboardLayout::Qwerty);
+        assert_eq!(layout(&["--keyboard", "azerty"]), KeyboardLayout::Azerty);
+        assert_eq!(layout(&["--keyboard", "dvorak"]), KeyboardLayout::Dvorak);
+        assert!(Args::try_parse_from(["wordlet", "--keyboard", "colemak"]).is_err());
+    }
+
+    #[test]
+    fn test_misspelled_names_are_rejected() {
+        for difficulty in GameDifficulty::value_variants() {
+            let args = Args::try_parse_from(["wordlet", "--difficulty", difficulty.name()]);
+            assert_eq!(args.unwrap().difficulty, Some(*difficulty));
+        }
+        for theme in ThemeName::value_variants() {
+            let args = Args::try_parse_from(["wordlet", "--theme", theme.name()]);
+            assert_eq!(args.unwrap().theme, Some(*theme));
+        }
+
+        let error = Args::try_parse_from(["wordlet", "--difficulty", "harde"]).unwrap_err();
+        assert!(error.to_string().contains("hard"));
+        assert!(Args::try_parse_from(["wordlet", "--theme", "lite"]).is_err());
+        assert!(Args::try_parse_from(["wordlet", "--batch", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_the_tick_has_to_be_at_least_a_millisecond() {
+        assert_eq!(Args::parse_from(["wordlet"]).tick_ms, 100);
+        assert_eq!(Args::parse_from(["wordlet", "--tick-ms", "40"]).tick_ms, 40);
+        assert!(Args::try_parse_from(["wordlet", "--tick-ms", "0"]).is_err());
+        assert!(Args::try_parse_from(["wordlet", "--tick-ms", "fast"]).is_err());
+    }
+
+    #[test]
+    fn test_practice_is_only_for_random_puzzles() {
+        assert!(Args::try_parse_from(["wordlet", "--practice"]).is_ok());
+        for fixed in [&["--daily"][..], &["--seed", "3"], &["--answer", "slump"]] {
+            let args = ["wordlet", "--practice"].iter().chain(fixed).copied();
+            assert!(Args::try_parse_from(args).is_err());
+        }
+    }
+
+    #[test]
+    fn test_the_assist_level_defaults_to_everything() {
+        let level = |flags: &[&str]| {
+            let args = ["wordlet"].iter().chain(flags).copied();
+            Args::try_parse_from(args).map(|args| assist_level(&args))
+        };
+        assert_eq!(level(&[]).unwrap(), AssistLevel::Candidates);
+        assert_eq!(level(&["--assist-level", "0"]).unwrap(), AssistLevel::Off);
+        assert_eq!(level(&["--assist-level", "1"]).unwrap(), AssistLevel::Hints);
+        assert!(level(&["--assist-level", "3"]).is_err());
+    }
+
+    #[test]
+    fn test_a_real_answer_is_used() {
+        let options = options_for(&["--answer", "SLUMP"]).unwrap();
+        assert_eq!(options.answer, Some("slump".to_string()));
+    }
+}
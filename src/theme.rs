@@ -1,8 +1,36 @@
+use crate::config::{parse_string, read_settings, ConfigError};
+use clap::ArgEnum;
+use std::fs;
+use std::path::Path;
 use tui::{
     style::{Color, Modifier},
     widgets::BorderType,
 };
 
+// every theme that can be picked by name, which --theme and the config file both take
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ThemeName {
+    Dark,
+    Light,
+    Truecolor,
+}
+
+impl ThemeName {
+    pub fn name(self) -> &'static str {
+        self.to_possible_value()
+            .expect("every theme has a name")
+            .get_name()
+    }
+
+    pub fn theme(self) -> Theme {
+        match self {
+            ThemeName::Dark => Theme::dark_theme(),
+            ThemeName::Light => Theme::light_theme(),
+            ThemeName::Truecolor => Theme::truecolor_theme(),
+        }
+    }
+}
+
 pub struct Theme {
     pub active_row_input_color: Color,
     pub border_color: Color,
@@ -19,6 +47,11 @@ pub struct Theme {
     pub row_border_thickness: BorderType,
     pub guessed_row_border_thickness: BorderType,
     pub welcome_message_color: Color,
+    // the border of the cell the next letter goes in
+    pub cursor_color: Color,
+    // marks each guessed letter with a symbol as well as a color, for anyone who can't tell the
+    // colors apart
+    pub use_symbols: bool,
 }
 
 impl Default for Theme {
@@ -33,6 +66,7 @@ impl Theme {
             border_color: Color::Black,
             active_row_input_color: Color::Black,
             welcome_message_color: Color::Black,
+            cursor_color: Color::Blue,
             header_text_success_color: Color::Green,
             header_text_error_color: Color::Red,
             empty_row_block_color: Color::Gray,
@@ -45,6 +79,7 @@ impl Theme {
             keyboard_not_in_word_color: Color::Gray,
             row_border_thickness: BorderType::Plain,
             guessed_row_border_thickness: BorderType::Thick,
+            use_symbols: false,
         }
     }
 
@@ -53,11 +88,173 @@ impl Theme {
             border_color: Color::White,
             active_row_input_color: Color::White,
             welcome_message_color: Color::White,
+            cursor_color: Color::LightCyan,
             keyboard_not_guessed_color: Color::White,
             keyboard_not_in_word_color: Color::Gray,
             ..Theme::light_theme()
         }
     }
+
+    // The dark theme with wordle's own colors, for terminals that can show 24-bit color.
+    // Terminals that can't will pick the nearest colors they have, or get them wrong.
+    pub fn truecolor_theme() -> Self {
+        let green = Color::Rgb(0x6a, 0xaa, 0x64);
+        let yellow = Color::Rgb(0xc9, 0xb4, 0x58);
+        let gray = Color::Rgb(0x78, 0x7c, 0x7e);
+
+        Theme {
+            header_text_success_color: green,
+            header_text_error_color: Color::Rgb(0xe0, 0x5d, 0x5d),
+            empty_row_block_color: Color::Rgb(0x3a, 0x3a, 0x3c),
+            guess_in_right_place_color: green,
+            guess_in_word_color: yellow,
+            guess_not_in_word_color: gray,
+            keyboard_in_right_place_color: green,
+            keyboard_in_word_color: yellow,
+            keyboard_not_in_word_color: Color::Rgb(0x3a, 0x3a, 0x3c),
+            ..Theme::dark_theme()
+        }
+    }
+
+    // in the order --list-themes shows them
+    pub fn built_in() -> Vec<(&'static str, Theme)> {
+        ThemeName::value_variants()
+            .iter()
+            .map(|name| (name.name(), name.theme()))
+            .collect()
+    }
+
+    // the colors a guessed letter can get, and the color of the text, on one line
+    pub fn summary(&self) -> String {
+        format!(
+            "right spot {}, in the word {}, not in the word {}, text {}",
+            color_name(self.guess_in_right_place_color),
+            color_name(self.guess_in_word_color),
+            color_name(self.guess_not_in_word_color),
+            color_name(self.active_row_input_color),
+        )
+    }
+
+    // Reads a theme like this, one line per color. Anything left out comes from the dark theme.
+    //
+    //   border_color = "white"
+    //   guess_in_right_place_color = "#6aaa64"
+    //   guessed_row_border_thickness = "rounded"
+    pub fn from_file(path: &Path) -> Result<Theme, ConfigError> {
+        let contents =
+            fs::read_to_string(path).map_err(|e| ConfigError::Unreadable(e.to_string()))?;
+        let mut theme = Theme::default();
+
+        for (key, value) in read_settings(&contents)? {
+            let flag = value.parse::<bool>();
+            let value = parse_string(value);
+            let invalid = || ConfigError::InvalidValue(key.to_string());
+            let color = || value.as_deref().and_then(parse_color).ok_or_else(invalid);
+            let border = || {
+                value
+                    .as_deref()
+                    .and_then(parse_border_type)
+                    .ok_or_else(invalid)
+            };
+
+            match key {
+                "active_row_input_color" => theme.active_row_input_color = color()?,
+                "border_color" => theme.border_color = color()?,
+                "header_text_error_color" => theme.header_text_error_color = color()?,
+                "header_text_success_color" => theme.header_text_success_color = color()?,
+                "empty_row_block_color" => theme.empty_row_block_color = color()?,
+                "guess_in_right_place_color" => theme.guess_in_right_place_color = color()?,
+                "guess_in_word_color" => theme.guess_in_word_color = color()?,
+                "guess_not_in_word_color" => theme.guess_not_in_word_color = color()?,
+                "keyboard_not_guessed_color" => theme.keyboard_not_guessed_color = color()?,
+                "keyboard_in_right_place_color" => theme.keyboard_in_right_place_color = color()?,
+                "keyboard_in_word_color" => theme.keyboard_in_word_color = color()?,
+                "keyboard_not_in_word_color" => theme.keyboard_not_in_word_color = color()?,
+                "welcome_message_color" => theme.welcome_message_color = color()?,
+                "cursor_color" => theme.cursor_color = color()?,
+                "row_border_thickness" => theme.row_border_thickness = border()?,
+                "guessed_row_border_thickness" => theme.guessed_row_border_thickness = border()?,
+                "use_symbols" => theme.use_symbols = flag.map_err(|_| invalid())?,
+                _ => return Err(ConfigError::UnknownKey(key.to_string())),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+// Either one of the 16 terminal colors by name, like "light_blue", or "#rrggbb"
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    let name: String = value
+        .chars()
+        .filter(|c| *c != '_' && *c != '-' && *c != ' ')
+        .collect::<String>()
+        .to_lowercase();
+    let color = match name.as_ref() {
+        "reset" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+// the name a theme file would use for the color
+fn color_name(color: Color) -> String {
+    let name = match color {
+        Color::Rgb(r, g, b) => return format!("#{r:02x}{g:02x}{b:02x}"),
+        Color::Indexed(i) => return format!("color {i}"),
+        Color::Reset => "reset",
+        Color::Black => "black",
+        Color::Red => "red",
+        Color::Green => "green",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "magenta",
+        Color::Cyan => "cyan",
+        Color::Gray => "gray",
+        Color::DarkGray => "dark_gray",
+        Color::LightRed => "light_red",
+        Color::LightGreen => "light_green",
+        Color::LightYellow => "light_yellow",
+        Color::LightBlue => "light_blue",
+        Color::LightMagenta => "light_magenta",
+        Color::LightCyan => "light_cyan",
+        Color::White => "white",
+    };
+    name.to_string()
+}
+
+fn parse_border_type(value: &str) -> Option<BorderType> {
+    match value.to_lowercase().as_ref() {
+        "plain" => Some(BorderType::Plain),
+        "rounded" => Some(BorderType::Rounded),
+        "double" => Some(BorderType::Double),
+        "thick" => Some(BorderType::Thick),
+        _ => None,
+    }
 }
 
 pub struct BlockTheme {
@@ -65,4 +262,120 @@ pub struct BlockTheme {
     pub border_color: Color,
     pub border_thickness: BorderType,
     pub text_color: Color,
+    // drawn in the top left corner of the cell's border
+    pub marker: Option<&'static str>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("wordlet-theme-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_a_theme_file_sets_colors_by_name_and_hex() {
+        let path = theme_file(
+            "sample.toml",
+            r##"
+            # a greener wordlet
+            border_color = "light_blue"
+            guess_in_right_place_color = "#6aaa64"
+            guessed_row_border_thickness = "rounded"
+            use_symbols = true
+            "##,
+        );
+        let theme = Theme::from_file(&path).unwrap();
+
+        assert_eq!(theme.border_color, Color::LightBlue);
+        assert_eq!(
+            theme.guess_in_right_place_color,
+            Color::Rgb(0x6a, 0xaa, 0x64)
+        );
+        assert_eq!(theme.guessed_row_border_thickness, BorderType::Rounded);
+        assert!(theme.use_symbols);
+        // left out, so it comes from the dark theme
+        assert_eq!(
+            theme.guess_in_word_color,
+            Theme::dark_theme().guess_in_word_color
+        );
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_hex_colors_are_parsed_into_rgb() {
+        assert_eq!(parse_color("#6aaa64"), Some(Color::Rgb(106, 170, 100)));
+        assert_eq!(parse_color("#FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_color("#000000"), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(parse_color("#6aaa6"), None);
+        assert_eq!(parse_color("#6aaa6g"), None);
+        assert_eq!(parse_color("6aaa64"), None);
+    }
+
+    #[test]
+    fn test_every_built_in_theme_is_listed() {
+        let names: Vec<&str> = Theme::built_in().iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["dark", "light", "truecolor"]);
+
+        let (_, truecolor) = &Theme::built_in()[2];
+        assert_eq!(
+            truecolor.summary(),
+            "right spot #6aaa64, in the word #c9b458, not in the word #787c7e, text white"
+        );
+    }
+
+    #[test]
+    fn test_themes_are_found_by_name() {
+        for name in ThemeName::value_variants() {
+            assert_eq!(ThemeName::from_str(name.name(), false), Ok(*name));
+        }
+        let light = ThemeName::from_str("light", false).unwrap().theme();
+        assert_eq!(light.border_color, Theme::light_theme().border_color);
+        assert_eq!(
+            ThemeName::Truecolor.theme().guess_in_word_color,
+            Theme::truecolor_theme().guess_in_word_color
+        );
+    }
+
+    #[test]
+    fn test_unknown_theme_names_are_not_found() {
+        for name in ["", "lite", "Dark", "dark ", "solarized"] {
+            assert!(
+                ThemeName::from_str(name, false).is_err(),
+                "{name} was found"
+            );
+        }
+    }
+
+    #[test]
+    fn test_color_names_can_be_read_back() {
+        for color in [
+            Color::LightBlue,
+            Color::DarkGray,
+            Color::Rgb(0x6a, 0xaa, 0x64),
+        ] {
+            assert_eq!(parse_color(&color_name(color)), Some(color));
+        }
+    }
+
+    #[test]
+    fn test_a_bad_theme_file_is_rejected() {
+        let path = theme_file("bad-color.toml", "border_color = \"plaid\"");
+        assert_eq!(
+            Theme::from_file(&path).err(),
+            Some(ConfigError::InvalidValue(String::from("border_color")))
+        );
+
+        let path = theme_file("bad-key.toml", "background = \"black\"");
+        assert_eq!(
+            Theme::from_file(&path).err(),
+            Some(ConfigError::UnknownKey(String::from("background")))
+        );
+        let _ = fs::remove_file(path);
+    }
 }
@@ -0,0 +1,71 @@
+// The tutorial is a normal game with a fixed answer. After every valid guess we move to the
+// next step, and the app shows that step's explanation in the header.
+pub const TUTORIAL_ANSWER: &str = "smile";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TutorialStep {
+    Introduction,
+    RightPlace,
+    InWord,
+    NotInWord,
+    Keyboard,
+    Finished,
+}
+
+impl TutorialStep {
+    pub fn next(&self) -> TutorialStep {
+        use TutorialStep::*;
+        match self {
+            Introduction => RightPlace,
+            RightPlace => InWord,
+            InWord => NotInWord,
+            NotInWord => Keyboard,
+            Keyboard | Finished => Finished,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        use TutorialStep::*;
+        match self {
+            Introduction => "This is a practice game. Type any five letter word and press Enter.",
+            RightPlace => "Green letters are in the answer, and in the right spot.",
+            InWord => "Yellow letters are in the answer, but in a different spot.",
+            NotInWord => "Dim letters are not in the answer at all. Try to avoid them.",
+            Keyboard => "The keyboard below shows every letter you've tried so far.",
+            Finished => "That's everything! Keep guessing until you find the word.",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tutorial_steps_advance_in_order() {
+        let mut step = TutorialStep::Introduction;
+        let mut seen = vec![step];
+
+        for _ in 0..5 {
+            step = step.next();
+            seen.push(step);
+        }
+
+        assert_eq!(
+            seen,
+            vec![
+                TutorialStep::Introduction,
+                TutorialStep::RightPlace,
+                TutorialStep::InWord,
+                TutorialStep::NotInWord,
+                TutorialStep::Keyboard,
+                TutorialStep::Finished,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tutorial_stays_finished() {
+        assert_eq!(TutorialStep::Finished.next(), TutorialStep::Finished);
+    }
+}
@@ -1,12 +1,15 @@
-use crate::app::{App, Disclaimer};
-use crate::engine::{GuessResult, HitAccuracy, RowState};
+use crate::app::{App, Disclaimer, EndScreen, KeyboardKey};
+use crate::batch::accuracy_word;
+use crate::engine::{Game, GameStatus, GuessResult, HitAccuracy, RowState};
 use crate::theme::BlockTheme;
+use std::time::Duration;
 use tui::{
     backend::Backend,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget, Wrap},
     Frame,
 };
 
@@ -17,43 +20,86 @@ pub enum Error {
 use Disclaimer::*;
 use GuessResult::*;
 
-const ROWS: usize = 6;
-const COLUMNS: usize = 5;
 const CELL_WIDTH: usize = 5;
 const CELL_HEIGHT: usize = 3;
 const PADDING: usize = 1;
+// the header and the keyboard each need their borders plus a few lines of text
+const HEADER_HEIGHT: usize = 5;
+const KEYBOARD_HEIGHT: usize = 8;
+
+// each board is one column per letter and one row per guess, and the boards sit side by side.
+// The header and keyboard get the same amount of room, above and below the grid
+pub fn minimum_terminal_size(boards: usize, columns: usize, rows: usize) -> (u16, u16) {
+    let width = (CELL_WIDTH * columns + 2 * PADDING) * boards + 2;
+    let height = CELL_HEIGHT * rows + 2 * PADDING + 2 + 2 * HEADER_HEIGHT.max(KEYBOARD_HEIGHT);
+    (
+        u16::try_from(width).unwrap_or(u16::MAX),
+        u16::try_from(height).unwrap_or(u16::MAX),
+    )
+}
+
+pub fn fits_in_terminal(
+    boards: usize,
+    columns: usize,
+    rows: usize,
+    width: u16,
+    height: u16,
+) -> bool {
+    let (min_width, min_height) = minimum_terminal_size(boards, columns, rows);
+    width >= min_width && height >= min_height
+}
 
 pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &mut App) -> Result<(), crate::ui::Error> {
     // a LOT of this code comes from a Minesweeper implementation in Rust, found at:
     // https://github.com/cpcloud/minesweep-rs/blob/main/src/ui.rs
+    let boards = app.session.boards().len();
+    let columns = app.game().word_length();
+    let rows = app.session.max_guesses();
     let terminal_rect = frame.size();
-    let grid_width =
-        u16::try_from(CELL_WIDTH * COLUMNS + 2 * PADDING).map_err(Error::ConvertUsizeToU16)?;
+    if !fits_in_terminal(
+        boards,
+        columns,
+        rows,
+        terminal_rect.width,
+        terminal_rect.height,
+    ) {
+        draw_terminal_too_small(frame, app, terminal_rect);
+        return Ok(());
+    }
+
+    let board_width =
+        u16::try_from(CELL_WIDTH * columns + 2 * PADDING).map_err(Error::ConvertUsizeToU16)?;
+    let grid_width = board_width * boards as u16;
     let grid_height =
-        u16::try_from(CELL_HEIGHT * ROWS + 2 * PADDING).map_err(Error::ConvertUsizeToU16)?;
+        u16::try_from(CELL_HEIGHT * rows + 2 * PADDING).map_err(Error::ConvertUsizeToU16)?;
 
-    let row_constraints = std::iter::repeat(Constraint::Length(
-        u16::try_from(CELL_HEIGHT).map_err(Error::ConvertUsizeToU16)?,
-    ))
-    .take(ROWS)
+    let row_constraints = std::iter::repeat_n(
+        Constraint::Length(u16::try_from(CELL_HEIGHT).map_err(Error::ConvertUsizeToU16)?),
+        rows,
+    )
     .collect::<Vec<_>>();
 
-    let col_constraints = std::iter::repeat(Constraint::Length(
-        u16::try_from(CELL_WIDTH).map_err(Error::ConvertUsizeToU16)?,
-    ))
-    .take(COLUMNS)
+    let col_constraints = std::iter::repeat_n(
+        Constraint::Length(u16::try_from(CELL_WIDTH).map_err(Error::ConvertUsizeToU16)?),
+        columns,
+    )
     .collect::<Vec<_>>();
 
+    // the status bar takes the line that would otherwise be the bottom margin, so the rest of
+    // the layout doesn't move
     let outer_rects = Layout::default()
         .direction(Direction::Vertical)
-        .vertical_margin(1)
         .horizontal_margin(1)
-        .constraints(vec![Constraint::Min(grid_height)])
+        .constraints(vec![
+            Constraint::Length(1),
+            Constraint::Min(grid_height),
+            Constraint::Length(1),
+        ])
         .split(frame.size());
 
-    let game_rectangle = outer_rects[0];
+    let game_rectangle = outer_rects[1];
 
-    let horizontal_pad_block_width = (terminal_rect.width - grid_width) / 2;
+    let horizontal_pad_block_width = terminal_rect.width.saturating_sub(grid_width) / 2;
     let center_center_horizontally = Layout::default()
         .direction(Direction::Horizontal)
         .constraints(vec![
@@ -63,7 +109,7 @@ pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &mut App) -> Result<(), crate
         ])
         .split(game_rectangle);
 
-    let vertical_pad_block_height = (game_rectangle.height - grid_height) / 2;
+    let vertical_pad_block_height = game_rectangle.height.saturating_sub(grid_height) / 2;
     let center_content_vertically = Layout::default()
         .direction(Direction::Vertical)
         .constraints(vec![
@@ -83,23 +129,86 @@ pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &mut App) -> Result<(), crate
         .constraints(vec![Constraint::Percentage(30), Constraint::Percentage(70)])
         .split(center_content_vertically[2]);
 
+    // The legend takes the room between the board and the keyboard, so the keyboard moves up
+    // rather than getting smaller. It can be wider than the board, and wraps when it isn't
+    let keyboard_chunk = match app.show_legend {
+        true => {
+            let legend_height = match legend_width() <= terminal_rect.width as usize {
+                true => 1,
+                false => 2,
+            };
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints(vec![
+                    Constraint::Min(0),
+                    Constraint::Length(keyboard_render_things[1].height),
+                    Constraint::Length(legend_height),
+                ])
+                .split(center_content_vertically[2]);
+            let legend_chunk = Rect {
+                x: terminal_rect.x,
+                width: terminal_rect.width,
+                ..chunks[2]
+            };
+            draw_legend(frame, app, legend_chunk);
+            chunks[1]
+        }
+        false => keyboard_render_things[1],
+    };
+
+    draw_header(frame, app, top_section_render_thing[0]);
+    draw_keyboard(frame, app, keyboard_chunk);
+    draw_status(frame, app, outer_rects[2]);
+
+    let board_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Length(board_width); boards])
+        .split(center_content_vertically[1]);
+
+    for (game, board_chunk) in app.session.boards().iter().zip(board_chunks) {
+        draw_board(
+            frame,
+            app,
+            game,
+            board_chunk,
+            &row_constraints,
+            &col_constraints,
+        );
+    }
+
+    match app.end_screen() {
+        // the last row gets to finish turning over before it's covered up
+        Some(_) if app.revealed_cells.is_some() => (),
+        Some(end_screen) => draw_end_screen(frame, app, &end_screen, terminal_rect),
+        None if app.show_help => draw_help(frame, app, terminal_rect),
+        None if app.show_letter_states => draw_letter_states(frame, app, terminal_rect),
+        None => (),
+    }
+
+    Ok(())
+}
+
+fn draw_board<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &App,
+    game: &Game,
+    game_board_section: Rect,
+    row_constraints: &[Constraint],
+    col_constraints: &[Constraint],
+) {
     let game_board = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded);
-
-    let game_board_section = center_content_vertically[1];
     frame.render_widget(game_board, game_board_section);
-    draw_header(frame, app, top_section_render_thing[0]);
-    draw_keyboard(frame, app, keyboard_render_things[1]);
 
     let row_chunks = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
         .horizontal_margin(0)
-        .constraints(row_constraints.clone())
+        .constraints(row_constraints.to_vec())
         .split(game_board_section);
 
-    let board_state = app.game.row_states();
+    let board_state = game.row_states();
 
     for (row_index, row_chunk) in row_chunks.into_iter().enumerate() {
         let row = board_state[row_index];
@@ -108,24 +217,273 @@ pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &mut App) -> Result<(), crate
             .direction(Direction::Horizontal)
             .vertical_margin(0)
             .horizontal_margin(1)
-            .constraints(col_constraints.clone())
+            .constraints(col_constraints.to_vec())
             .split(row_chunk);
 
         match row {
             RowState::Current => render_active_row(frame, app, chunks),
             RowState::Empty => render_empty_row(frame, app, chunks),
-            RowState::AlreadyGuessed => render_already_guessed_row(frame, app, row_index, chunks),
+            RowState::AlreadyGuessed => {
+                render_already_guessed_row(frame, app, game, row_index, chunks)
+            }
         }
     }
+}
 
-    Ok(())
+const HELP_TEXT: &str = "Guess the hidden word. Every guess has to be a real word.
+
+Green letters are in the right spot. Yellow letters are in the word, but somewhere else. \
+Gray letters are not in the word at all.
+
+In hard mode, every letter you've found has to be used again, and green letters have to stay \
+where they are. Ultra mode also rules out gray letters. In absurd mode there's no answer \
+until there has to be one, and every guess gets whichever colors keep the most words possible.
+
+Type to enter a word, Backspace to fix it and Enter to guess, or click the keys on the \
+keyboard. Tab reveals a letter, and ! lists what every letter is known to be. Up and Down \
+step through your earlier guesses. Ctrl+G gives up and shows the answer. Esc quits, once you \
+press y to confirm.
+
+Press ? or Esc to close this.";
+
+// dims everything that has already been drawn, so a popup stands out from the board
+struct Dim;
+
+impl Widget for Dim {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y).modifier.insert(Modifier::DIM);
+            }
+        }
+    }
+}
+
+fn draw_help<B: Backend>(frame: &mut Frame<B>, app: &App, terminal_rect: Rect) {
+    draw_popup(
+        frame,
+        app,
+        terminal_rect,
+        "How to play",
+        HELP_TEXT.to_string(),
+        22,
+    );
+}
+
+// Uses the same symbols as --symbols, so the list fits over the narrowest board. Four letters to
+// a line keeps it short enough too
+fn letter_states_text(app: &App) -> String {
+    let states: Vec<String> = app
+        .letter_states()
+        .into_iter()
+        .map(|(letter, state)| format!("{letter} {}", state.map_or("·", accuracy_symbol)))
+        .collect();
+    let grid: Vec<String> = states.chunks(4).map(|line| line.join("   ")).collect();
+
+    format!(
+        "+ right spot, ~ in the word, x not in the word, · not played yet\n\n{}\n\nPress ! or Esc to close this.",
+        grid.join("\n")
+    )
+}
+
+fn draw_letter_states<B: Backend>(frame: &mut Frame<B>, app: &App, terminal_rect: Rect) {
+    let text = letter_states_text(app);
+    draw_popup(frame, app, terminal_rect, "Letters", text, 17);
+}
+
+fn end_screen_text(app: &App, end_screen: &EndScreen) -> String {
+    let score = match end_screen.won {
+        true => format!(
+            "You win! {}/{}",
+            end_screen.guesses_used, end_screen.max_guesses
+        ),
+        false => format!("Game over! X/{}", end_screen.max_guesses),
+    };
+
+    // the rating is for one word, so it's left out when there are several
+    let rating = match app.session.boards() {
+        [game] => game
+            .answer_difficulty()
+            .map_or_else(String::new, |d| format!("\nDifficulty: {d}/10.")),
+        _ => String::new(),
+    };
+
+    let meaning = match app.session.boards() {
+        [game] => game
+            .answer_definition()
+            .map_or_else(String::new, |meaning| format!("\nIt means {meaning}.")),
+        _ => String::new(),
+    };
+
+    let answers = match end_screen.answers.as_slice() {
+        [] => String::from("The answer stays hidden, so others can still try this puzzle."),
+        [answer] => format!("The answer was '{answer}'."),
+        answers => format!("The answers were '{}'.", answers.join("', '")),
+    };
+
+    let friend = match (&app.friend_grid, app.matched_friend_path) {
+        (Some(_), true) => "\nYou followed your friend's path exactly!",
+        (Some(_), false) => "\nYou took a different path than your friend.",
+        (None, _) => "",
+    };
+
+    let exit_prompt = match (app.auto_restart_ticks, app.restart_on_enter) {
+        _ if !app.can_restart() && app.restart_on_enter => {
+            "That was today's puzzle. Come back tomorrow, or press Esc to exit."
+        }
+        _ if !app.can_restart() => {
+            "That was today's puzzle. Come back tomorrow, or press any key to exit."
+        }
+        (Some(_), _) => "The next game starts in a moment. Press Esc to stop.",
+        (None, true) => "Press Enter to play again, or Esc to exit.",
+        (None, false) => "Press r to play again, or any other key to exit.",
+    };
+
+    format!(
+        "{score}\n\n{answers}{meaning}\nTime: {}{rating}{friend}\n\n{exit_prompt}",
+        format_duration(end_screen.solve_time)
+    )
+}
+
+fn draw_end_screen<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &App,
+    end_screen: &EndScreen,
+    terminal_rect: Rect,
+) {
+    let text = end_screen_text(app, end_screen);
+    // the stats go at the bottom, a line apart from the text. There are none before the first game
+    // is recorded
+    let stats_height = match app.stats.games_played {
+        0 => 0,
+        _ => stats_lines(app, 0).len() as u16,
+    };
+    let popup = draw_popup(
+        frame,
+        app,
+        terminal_rect,
+        "Game over",
+        text,
+        14 + stats_height + 1,
+    );
+
+    let inner = popup.inner(&Margin {
+        horizontal: 1,
+        vertical: 1,
+    });
+    if stats_height > 0 && inner.height > stats_height {
+        let chunk = Rect::new(
+            inner.x,
+            inner.bottom() - stats_height,
+            inner.width,
+            stats_height,
+        );
+        draw_stats(frame, app, chunk);
+    }
+}
+
+// The totals, then a bar for each number of guesses, as long as the wins it took that many
+// guesses, like Wordle's stats. The bar for the game that just ended stands out
+fn stats_lines(app: &App, width: u16) -> Vec<Spans<'static>> {
+    let stats = &app.stats;
+    let rows = stats.distribution.len().max(app.session.max_guesses());
+    let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1);
+    let label_width = rows.to_string().len();
+    let count_width = most.to_string().len();
+    let bar_room = (width as usize).saturating_sub(label_width + count_width + 2);
+    let just_won = app
+        .end_screen()
+        .filter(|end_screen| end_screen.won)
+        .map(|end_screen| end_screen.guesses_used);
+
+    let totals = Spans::from(Span::styled(
+        format!(
+            "Played {}  Win % {}  Streak {}  Max {}",
+            stats.games_played,
+            stats.win_percentage(),
+            stats.current_streak,
+            stats.max_streak
+        ),
+        Style::default().fg(app.theme.welcome_message_color),
+    ));
+
+    let bars = (1..=rows).map(|guesses| {
+        let wins = stats.distribution.get(guesses - 1).copied().unwrap_or(0);
+        let length = (wins as usize * bar_room / most as usize).max(1);
+        let color = match just_won == Some(guesses) {
+            true => app.theme.keyboard_in_right_place_color,
+            false => app.theme.keyboard_not_guessed_color,
+        };
+        Spans::from(vec![
+            Span::styled(
+                format!("{guesses:>label_width$} "),
+                Style::default().fg(app.theme.welcome_message_color),
+            ),
+            Span::styled("█".repeat(length), Style::default().fg(color)),
+            Span::styled(format!(" {wins}"), Style::default().fg(color)),
+        ])
+    });
+
+    std::iter::once(totals).chain(bars).collect()
+}
+
+pub fn draw_stats<B: Backend>(frame: &mut Frame<B>, app: &App, chunk: Rect) {
+    let paragraph = Paragraph::new(stats_lines(app, chunk.width));
+    frame.render_widget(paragraph, chunk);
 }
 
-pub fn render_empty_row<B: Backend>(
+// draws the text in a box in the middle of the terminal, over a dimmed board, and says where
+fn draw_popup<B: Backend>(
     frame: &mut Frame<B>,
-    app: &mut App,
-    cell_chunks: Vec<Rect>,
-) -> () {
+    app: &App,
+    terminal_rect: Rect,
+    title: &str,
+    text: String,
+    max_height: u16,
+) -> Rect {
+    let width = terminal_rect.width.saturating_sub(4).min(60);
+    let height = terminal_rect.height.saturating_sub(4).min(max_height);
+    let popup = Rect::new(
+        terminal_rect.x + (terminal_rect.width - width) / 2,
+        terminal_rect.y + (terminal_rect.height - height) / 2,
+        width,
+        height,
+    );
+
+    let paragraph = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .style(Style::default().fg(app.theme.welcome_message_color))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .style(Style::default().fg(app.theme.border_color))
+                .title(title),
+        );
+
+    frame.render_widget(Dim, terminal_rect);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(paragraph, popup);
+    popup
+}
+
+fn draw_terminal_too_small<B: Backend>(frame: &mut Frame<B>, app: &App, chunk: Rect) {
+    let (min_width, min_height) = minimum_terminal_size(
+        app.session.boards().len(),
+        app.game().word_length(),
+        app.session.max_guesses(),
+    );
+    let message = Paragraph::new(format!(
+        "Terminal too small. Please resize it to at least {min_width}x{min_height}."
+    ))
+    .wrap(Wrap { trim: true })
+    .style(Style::default().fg(app.theme.header_text_error_color))
+    .alignment(Alignment::Center);
+
+    frame.render_widget(message, chunk);
+}
+
+pub fn render_empty_row<B: Backend>(frame: &mut Frame<B>, app: &App, cell_chunks: Vec<Rect>) {
     for cell_chunk in cell_chunks.into_iter() {
         let content = render_cell_with_text_and_colors(
             " ".to_string(),
@@ -134,6 +492,7 @@ pub fn render_empty_row<B: Backend>(
                 text_color: app.theme.empty_row_block_color,
                 border_thickness: app.theme.row_border_thickness,
                 border_brightness: Modifier::empty(),
+                marker: None,
             },
         );
 
@@ -141,25 +500,29 @@ pub fn render_empty_row<B: Backend>(
     }
 }
 
-pub fn render_active_row<B: Backend>(
-    frame: &mut Frame<B>,
-    app: &mut App,
-    cell_chunks: Vec<Rect>,
-) -> () {
+pub fn render_active_row<B: Backend>(frame: &mut Frame<B>, app: &App, cell_chunks: Vec<Rect>) {
     let mut chars = app.input.chars();
+    // the next letter goes here. Once the row is full this is past the end, so there's no cursor
+    let cursor = app.input.chars().count();
 
-    for cell_chunk in cell_chunks.into_iter() {
+    for (index, cell_chunk) in cell_chunks.into_iter().enumerate() {
         let text = match chars.next() {
             Some(l) => l.to_string(),
             _ => " ".to_string(),
         };
+        let border_color = match (app.error_flash, index == cursor) {
+            (Some(_), _) => app.theme.header_text_error_color,
+            (None, true) => app.theme.cursor_color,
+            (None, false) => app.theme.border_color,
+        };
         let content = render_cell_with_text_and_colors(
             text,
             BlockTheme {
-                border_color: app.theme.border_color,
+                border_color,
                 text_color: app.theme.active_row_input_color,
                 border_thickness: app.theme.row_border_thickness,
                 border_brightness: Modifier::empty(),
+                marker: None,
             },
         );
         frame.render_widget(content, cell_chunk);
@@ -168,17 +531,47 @@ pub fn render_active_row<B: Backend>(
 
 pub fn render_already_guessed_row<B: Backend>(
     frame: &mut Frame<B>,
-    app: &mut App,
+    app: &App,
+    game: &Game,
     row_index: usize,
     chunks: Vec<Rect>,
-) -> () {
-    if let Some(word_guess) = app.game.guesses().get(row_index) {
+) {
+    // Only the newest row turns over. A board that was solved before the last guess didn't get
+    // it, so its newest row is an older one
+    let newest_row = app.session.guesses_used();
+    let revealed = match app.revealed_cells {
+        Some(cells) if row_index + 1 == newest_row && game.guesses().len() == newest_row => cells,
+        _ => game.word_length(),
+    };
+
+    // the row picked with the arrow keys stands out from the others
+    let border_thickness = match app.reviewed_row() == Some(row_index) {
+        true => BorderType::Double,
+        false => app.theme.guessed_row_border_thickness,
+    };
+
+    if let Some(word_guess) = game.guesses().get(row_index) {
         let items = chunks.iter().zip(word_guess.letters.iter());
 
-        for (chunk, guess_letter) in items {
+        for (index, (chunk, guess_letter)) in items.enumerate() {
             let character = guess_letter.letter.to_string();
             let accuracy = guess_letter.accuracy;
 
+            if index >= revealed {
+                let content = render_cell_with_text_and_colors(
+                    character,
+                    BlockTheme {
+                        border_color: app.theme.border_color,
+                        text_color: app.theme.active_row_input_color,
+                        border_thickness: app.theme.row_border_thickness,
+                        border_brightness: Modifier::empty(),
+                        marker: None,
+                    },
+                );
+                frame.render_widget(content, *chunk);
+                continue;
+            }
+
             let color = match accuracy {
                 HitAccuracy::InRightPlace => app.theme.guess_in_right_place_color,
                 HitAccuracy::InWord => app.theme.guess_in_word_color,
@@ -190,13 +583,16 @@ pub fn render_already_guessed_row<B: Backend>(
                 _ => Modifier::empty(),
             };
 
+            let marker = app.theme.use_symbols.then(|| accuracy_symbol(accuracy));
+
             let content = render_cell_with_text_and_colors(
                 character,
                 BlockTheme {
                     border_color: color,
                     text_color: color,
-                    border_thickness: app.theme.guessed_row_border_thickness,
+                    border_thickness,
                     border_brightness: brightness,
+                    marker,
                 },
             );
 
@@ -205,15 +601,28 @@ pub fn render_already_guessed_row<B: Backend>(
     }
 }
 
+// the cells are only three characters wide inside, so the symbols go on the border
+fn accuracy_symbol(accuracy: HitAccuracy) -> &'static str {
+    match accuracy {
+        HitAccuracy::InRightPlace => "+",
+        HitAccuracy::InWord => "~",
+        HitAccuracy::NotInWord => "x",
+    }
+}
+
 pub fn render_cell_with_text_and_colors(
     text: String,
     block_theme: BlockTheme,
 ) -> Paragraph<'static> {
     let text = formatted_cell_text(text);
+    let block = match block_theme.marker {
+        Some(marker) => Block::default().title(marker),
+        None => Block::default(),
+    };
 
     Paragraph::new(text)
         .block(
-            Block::default()
+            block
                 .borders(Borders::ALL)
                 .border_type(block_theme.border_thickness)
                 .border_style(Style::default().fg(block_theme.border_color))
@@ -234,51 +643,112 @@ fn formatted_cell_text(text: String) -> String {
     let pad_line = " ".repeat(CELL_WIDTH);
     let num_pad_lines = CELL_HEIGHT - 3;
 
-    std::iter::repeat(pad_line.clone())
-        .take(num_pad_lines / 2)
+    std::iter::repeat_n(pad_line.clone(), num_pad_lines / 2)
         .chain(std::iter::once(single_row_text.clone()))
-        .chain(std::iter::repeat(pad_line).take(num_pad_lines / 2))
+        .chain(std::iter::repeat_n(pad_line, num_pad_lines / 2))
         .collect::<Vec<_>>()
         .join("\n")
 }
 
 pub fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
+    // the details of a finished game are on the end screen, so the header only needs to say
+    // how it went
     let text = match &app.disclaimer {
-        Some(GameWonMessage) => String::from("Game is over! You win! Press any key to exit."),
-        Some(GameOverMessage(answer)) => {
-            format!("Game over! The answer was '{answer}'. Press any key to exit.")
+        _ if app.confirm_quit => String::from("Quit? (y/n)"),
+        Some(GameWonMessage) => String::from("You win!"),
+        Some(GameOverMessage(_)) => String::from("Game over!"),
+        Some(FriendRowMessage(row, true)) => {
+            format!("Your colors match your friend's guess number {row}.")
         }
-        Some(MoveFeedback(f)) => match f {
-            DoesNotIncludeRequiredLetter(letter) => {
-                format!("Does not include the required letter '{letter}'")
-            }
-            LetterDoesNotMatch(ch, idx) => {
-                let number = match idx {
-                    1 => "1st".to_string(),
-                    2 => "2nd".to_string(),
-                    3 => "3rd".to_string(),
-                    _ => format!("{ch}th"),
-                };
-                format!("The {number} letter must be '{ch}'")
+        Some(FriendRowMessage(row, false)) => {
+            format!("Your colors are different from your friend's guess number {row}.")
+        }
+        Some(ReviewedRowMessage(row)) => match app.game().guesses().get(*row) {
+            Some(guess) => {
+                let letters: Vec<String> = guess
+                    .letters()
+                    .iter()
+                    .map(|gl| format!("{}={}", gl.letter, accuracy_word(gl.accuracy)))
+                    .collect();
+                format!("Guess {}: {}", row + 1, letters.join(" "))
             }
-            IncorrectCharacterCount => String::from("Your guess must be 5 characters long!"),
-            NotInDictionary => String::from("Not a valid word!"),
-            DuplicateGuess => String::from("You already guessed that!"),
-            GameIsAlreadyOver => String::from("The game is already over!"),
-            Valid => String::from(""),
+            None => String::new(),
         },
+        Some(MoveFeedback(f)) => move_feedback_message(f, app.game().word_length()),
+        Some(HardModeWarning(f)) => format!(
+            "{}. Hard mode wouldn't allow it, but it counts.",
+            move_feedback_message(f, app.game().word_length())
+        ),
+        Some(IdleHintMessage(hint)) => hint.to_string(),
+        Some(CheatAnswerMessage(Some(answers))) => format!("The answer is '{answers}'."),
+        Some(CheatAnswerMessage(None)) => {
+            String::from("There's no answer yet. It keeps changing until one word is left.")
+        }
+        Some(NoValidGuessesMessage) => String::from(
+            "No word in the dictionary fits every clue, so there is nothing left to play. Press Esc to exit.",
+        ),
+        Some(TutorialMessage(step)) => String::from(step.message()),
         Some(WelcomeMessage) => {
-            String::from("Welcome to Wordlet. You have six tries to guess the answer. Good luck!")
+            let welcome = match app.session.boards().len() {
+                1 => format!(
+                    "Welcome to Wordlet. You have {} tries to guess the answer. Good luck!",
+                    app.session.max_guesses()
+                ),
+                boards => format!(
+                    "Welcome to Wordlet. You have {} tries to guess all {boards} answers. Good luck!",
+                    app.session.max_guesses()
+                ),
+            };
+            match &app.opener {
+                Some(opener) => format!("{welcome} Try '{opener}' to start."),
+                None => welcome,
+            }
+        }
+        Some(NewGameMessage) => String::from("Here's a new word. Good luck!"),
+        Some(DailyDoneMessage) => String::from(
+            "You've played today's puzzle. Come back tomorrow, or press any key to exit.",
+        ),
+        Some(ResumedGameMessage) => format!(
+            "Welcome back. You have {} tries left.",
+            app.session.max_guesses().saturating_sub(app.session.guesses_used())
+        ),
+        Some(LetterHintMessage(position, letter)) => {
+            format!("Hint: the {} letter is '{letter}'.", ordinal(*position))
         }
         None => String::from(""),
     };
 
     let header_text_color = match &app.disclaimer {
+        _ if app.confirm_quit => app.theme.header_text_error_color,
         Some(GameWonMessage) => app.theme.header_text_success_color,
-        Some(WelcomeMessage) => app.theme.welcome_message_color,
+        Some(FriendRowMessage(_, true)) => app.theme.header_text_success_color,
+        Some(WelcomeMessage)
+        | Some(NewGameMessage)
+        | Some(DailyDoneMessage)
+        | Some(ResumedGameMessage)
+        | Some(TutorialMessage(_))
+        | Some(IdleHintMessage(_))
+        | Some(ReviewedRowMessage(_))
+        | Some(CheatAnswerMessage(_)) => app.theme.welcome_message_color,
         _ => app.theme.header_text_error_color,
     };
 
+    // the clock and the guess number are in the status bar
+    let title = match app.show_guess_dots {
+        true => format!(
+            "Wordlet {}",
+            draw_guess_dots(app.session.guesses_used(), app.session.max_guesses())
+        ),
+        false => String::from("Wordlet"),
+    };
+    // absurd mode keeps dodging, so the player gets to see how many words it has left to hide in
+    let title = match app.game().candidates_left() {
+        Some(left) if app.session.game_status() == GameStatus::InProgress => {
+            format!("{title} {left} words left")
+        }
+        _ => title,
+    };
+
     let header_text = Paragraph::new(text)
         .wrap(Wrap { trim: true })
         .style(Style::default().fg(header_text_color))
@@ -287,33 +757,176 @@ pub fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect)
             Block::default()
                 .borders(Borders::ALL)
                 .style(Style::default().fg(app.theme.border_color))
-                .title("Wordlet")
+                .title(title)
                 .border_type(BorderType::Plain),
         );
 
     frame.render_widget(header_text, chunk);
 }
 
-pub fn draw_keyboard<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
-    let keyboard_key_rows = vec!["qwertyuiop", "asdfghjkl", "zxcvbnm"];
-    let keyboard_spans = keyboard_key_rows
+// positions count from 1. Longer boards can get past the 10th letter, where 11th, 12th and 13th
+// break the usual 1st, 2nd and 3rd pattern
+fn ordinal(position: usize) -> String {
+    let suffix = match (position % 10, position % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{position}{suffix}")
+}
+
+// why a guess was turned down, or in guided mode which hard mode rule it broke
+fn move_feedback_message(result: &GuessResult, word_length: usize) -> String {
+    match result {
+        DoesNotIncludeRequiredLetter(letter) => {
+            format!("Does not include the required letter '{letter}'")
+        }
+        LetterDoesNotMatch(ch, idx) => letter_must_match_message(*ch, *idx),
+        UsesEliminatedLetter(letter) => {
+            format!("'{letter}' is already known not to be in the word")
+        }
+        IncorrectCharacterCount => format!("Your guess must be {word_length} characters long!"),
+        NotInDictionary => String::from("Not a valid word!"),
+        DuplicateGuess => String::from("You already guessed that!"),
+        GameIsAlreadyOver => String::from("The game is already over!"),
+        Valid => String::from(""),
+    }
+}
+
+fn letter_must_match_message(letter: char, position: usize) -> String {
+    format!("The {} letter must be '{letter}'", ordinal(position))
+}
+
+// minutes and seconds, like 1:05. Long games just keep counting minutes
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+// the guess being played out of the guesses allowed, and the game clock. Once the game is over
+// it's the number of guesses it took
+fn status_text(app: &App) -> String {
+    let used = app.session.guesses_used();
+    let max_guesses = app.session.max_guesses();
+    let guess = match app.session.game_status() {
+        GameStatus::InProgress => (used + 1).min(max_guesses),
+        _ => used,
+    };
+    format!(
+        "Guess {guess}/{max_guesses} · {}",
+        format_duration(app.elapsed())
+    )
+}
+
+pub fn draw_status<B: Backend>(frame: &mut Frame<B>, app: &App, chunk: Rect) {
+    let status = Paragraph::new(status_text(app))
+        .style(Style::default().fg(app.theme.welcome_message_color))
+        .alignment(Alignment::Center);
+    frame.render_widget(status, chunk);
+}
+
+// one filled dot for every guess that has been used, and an empty dot for every one that's left
+pub fn draw_guess_dots(used: usize, max_guesses: usize) -> String {
+    let remaining = max_guesses.saturating_sub(used);
+
+    std::iter::repeat_n("●", used)
+        .chain(std::iter::repeat_n("○", remaining))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// The keys' labels, row by row. ENTER and ⌫ go at either end of the bottom row, like Wordle's
+// keyboard. Over a narrow board there isn't room to spell out ENTER, so it becomes ⏎
+fn keyboard_layout(keyboard_rows: &[String], width: u16) -> Vec<Vec<(KeyboardKey, String)>> {
+    let last_row = keyboard_rows.len().saturating_sub(1);
+    keyboard_rows
         .iter()
-        .fold(vec![], |mut acc, keyboard_row| {
-            // when we draw the keyboard, we want a blank space after every character
-            // except for the last character, so that we don't go off-center
-            let letters: Vec<Span> = keyboard_row
+        .enumerate()
+        .map(|(index, keyboard_row)| {
+            let letters = keyboard_row
                 .chars()
+                .map(|letter| (KeyboardKey::Letter(letter), letter.to_string()));
+            if index != last_row {
+                return letters.collect();
+            }
+
+            let enter = |label: &str| {
+                std::iter::once((KeyboardKey::Enter, String::from(label)))
+                    .chain(letters.clone())
+                    .chain(std::iter::once((KeyboardKey::Backspace, String::from("⌫"))))
+                    .collect::<Vec<_>>()
+            };
+            let row = enter("ENTER");
+            match line_width(&row) <= width {
+                true => row,
+                false => enter("⏎"),
+            }
+        })
+        .collect()
+}
+
+// keys are drawn with a space between each one
+fn line_width(keys: &[(KeyboardKey, String)]) -> u16 {
+    let labels: u16 = keys
+        .iter()
+        .map(|(_, label)| Span::raw(label.as_str()).width() as u16)
+        .sum();
+    labels + keys.len().saturating_sub(1) as u16
+}
+
+// Where each key is drawn inside the keyboard's border. This has to lay the rows out the same
+// way the centered Paragraph does, one space between keys
+pub fn keyboard_key_rects(keyboard_rows: &[String], chunk: Rect) -> Vec<(Rect, KeyboardKey)> {
+    let inner = Block::default().borders(Borders::ALL).inner(chunk);
+    let mut rects = vec![];
+
+    for (row_index, keys) in keyboard_layout(keyboard_rows, inner.width)
+        .iter()
+        .enumerate()
+    {
+        let y = inner.y + row_index as u16;
+        if y >= inner.bottom() {
+            break;
+        }
+
+        let mut x = inner.x + (inner.width / 2).saturating_sub(line_width(keys) / 2);
+        for (key, label) in keys {
+            let width = Span::raw(label.as_str()).width() as u16;
+            if x + width > inner.right() {
+                break;
+            }
+            rects.push((Rect::new(x, y, width, 1), *key));
+            x += width + 1;
+        }
+    }
+
+    rects
+}
+
+pub fn draw_keyboard<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rect) {
+    let width = Block::default().borders(Borders::ALL).inner(chunk).width;
+    let keyboard_spans: Vec<Spans> = keyboard_layout(&app.keyboard_rows, width)
+        .into_iter()
+        .map(|keys| {
+            // when we draw the keyboard, we want a blank space after every key
+            // except for the last one, so that we don't go off-center
+            let row_length = keys.len();
+            let letters: Vec<Span> = keys
                 .into_iter()
                 .enumerate()
-                .map(|(letter_index, letter)| {
-                    let use_offset = letter_index != keyboard_row.len() - 1;
-                    keyboard_letter(&app, letter, use_offset)
+                .map(|(key_index, (key, label))| {
+                    let use_offset = key_index != row_length - 1;
+                    match key {
+                        KeyboardKey::Letter(letter) => keyboard_letter(app, letter, use_offset),
+                        _ => keyboard_control(app, label, use_offset),
+                    }
                 })
                 .collect();
-
-            acc.push(Spans::from(letters));
-            acc
-        });
+            Spans::from(letters)
+        })
+        .collect();
 
     let keyboard_visualization = Paragraph::new(keyboard_spans)
         .style(Style::default())
@@ -327,11 +940,64 @@ pub fn draw_keyboard<B: Backend>(frame: &mut Frame<B>, app: &mut App, chunk: Rec
         );
 
     frame.render_widget(keyboard_visualization, chunk);
+    app.keyboard_keys = keyboard_key_rects(&app.keyboard_rows, chunk);
+}
+
+const LEGEND: [(HitAccuracy, &str); 3] = [
+    (HitAccuracy::InRightPlace, "right spot"),
+    (HitAccuracy::InWord, "wrong spot"),
+    (HitAccuracy::NotInWord, "not in word"),
+];
+const LEGEND_GAP: &str = "   ";
+
+// a swatch and a space before each label, and a gap between them
+fn legend_width() -> usize {
+    let labels: usize = LEGEND.iter().map(|(_, label)| label.len() + 2).sum();
+    labels + LEGEND_GAP.len() * (LEGEND.len() - 1)
+}
+
+fn draw_legend<B: Backend>(frame: &mut Frame<B>, app: &App, chunk: Rect) {
+    let mut spans = vec![];
+    for (index, (accuracy, label)) in LEGEND.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw(LEGEND_GAP));
+        }
+        let color = match accuracy {
+            HitAccuracy::InRightPlace => app.theme.guess_in_right_place_color,
+            HitAccuracy::InWord => app.theme.guess_in_word_color,
+            HitAccuracy::NotInWord => app.theme.guess_not_in_word_color,
+        };
+        let swatch = match app.theme.use_symbols {
+            true => accuracy_symbol(*accuracy),
+            false => "█",
+        };
+        spans.push(Span::styled(swatch, Style::default().fg(color)));
+        spans.push(Span::styled(
+            format!(" {label}"),
+            Style::default().fg(app.theme.welcome_message_color),
+        ));
+    }
+
+    let legend = Paragraph::new(Spans::from(spans))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(legend, chunk);
+}
+
+fn keyboard_control(app: &App, label: String, use_offset: bool) -> Span<'static> {
+    let key_string = match use_offset {
+        true => format!("{label} "),
+        false => label,
+    };
+    Span::styled(
+        key_string,
+        Style::default().fg(app.theme.keyboard_not_guessed_color),
+    )
 }
 
 pub fn keyboard_letter<'a>(app: &'a App, le: char, use_offset: bool) -> Span<'a> {
     use HitAccuracy::*;
-    let key_state = app.game.get_letter_match_state(le);
+    let key_state = app.session.get_letter_match_state(le);
 
     let color = match key_state {
         None => app.theme.keyboard_not_guessed_color,
@@ -355,3 +1021,699 @@ pub fn keyboard_letter<'a>(app: &'a App, le: char, use_offset: bool) -> Span<'a>
         Style::default().fg(color).add_modifier(display_modifier),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppOptions;
+    use crate::engine::{GameOptions, Stats};
+    use crate::theme::Theme;
+    use crossterm::event::{KeyCode, KeyEvent};
+    use tui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    #[test]
+    fn test_durations_are_written_as_minutes_and_seconds() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "0:00");
+        assert_eq!(format_duration(Duration::from_millis(9_999)), "0:09");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1:05");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "60:00");
+    }
+
+    #[test]
+    fn test_letter_positions_are_written_as_ordinals() {
+        assert_eq!(
+            letter_must_match_message('s', 1),
+            "The 1st letter must be 's'"
+        );
+        assert_eq!(
+            letter_must_match_message('e', 4),
+            "The 4th letter must be 'e'"
+        );
+        assert_eq!(
+            letter_must_match_message('p', 5),
+            "The 5th letter must be 'p'"
+        );
+        assert_eq!(ordinal(2), "2nd");
+        assert_eq!(ordinal(3), "3rd");
+        assert_eq!(ordinal(11), "11th");
+        assert_eq!(ordinal(12), "12th");
+        assert_eq!(ordinal(21), "21st");
+    }
+
+    fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+        let width = buffer.area.width as usize;
+        buffer
+            .content
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol.as_str()).collect())
+            .collect()
+    }
+
+    fn game() -> Game {
+        Game::new(GameOptions {
+            answer: Some("slump".to_string()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_guess_dots_at_the_start_of_the_game() {
+        assert_eq!(draw_guess_dots(0, game().max_guesses()), "○ ○ ○ ○ ○ ○");
+    }
+
+    #[test]
+    fn test_guess_dots_in_the_middle_of_the_game() {
+        let mut game = game();
+        game.guess("admit");
+        game.guess("adorn");
+        game.guess("adult");
+        assert_eq!(
+            draw_guess_dots(game.guesses().len(), game.max_guesses()),
+            "● ● ● ○ ○ ○"
+        );
+    }
+
+    #[test]
+    fn test_guess_dots_at_the_end_of_the_game() {
+        let mut game = game();
+        for word in ["admit", "adorn", "adult", "affix", "afire", "aging"] {
+            game.guess(word);
+        }
+        assert_eq!(
+            draw_guess_dots(game.guesses().len(), game.max_guesses()),
+            "● ● ● ● ● ●"
+        );
+    }
+
+    #[test]
+    fn test_keyboard_renders_a_custom_layout() {
+        let mut app = App::new(AppOptions {
+            keyboard_rows: vec!["абвгд".to_string(), "еёжз".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 4)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                draw_keyboard(frame, &mut app, area);
+            })
+            .unwrap();
+
+        let lines = buffer_lines(terminal.backend().buffer());
+        assert!(lines[1].contains("а б в г д"));
+        assert!(lines[2].contains("е ё ж з"));
+        assert!(!lines.iter().any(|line| line.contains('q')));
+    }
+
+    #[test]
+    fn test_the_keyboard_remembers_where_each_key_was_drawn() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(30, 6)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                draw_keyboard(frame, &mut app, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert_eq!(app.keyboard_keys.len(), 26 + 2);
+        for (rect, key) in &app.keyboard_keys {
+            let symbol = &buffer.get(rect.x, rect.y).symbol;
+            match key {
+                KeyboardKey::Letter(letter) => assert_eq!(symbol, &letter.to_string()),
+                KeyboardKey::Enter => assert_eq!(symbol, "E"),
+                KeyboardKey::Backspace => assert_eq!(symbol, "⌫"),
+            }
+        }
+        let lines = buffer_lines(buffer);
+        assert!(lines[3].contains("ENTER z x c v b n m ⌫"));
+        let enter = app
+            .keyboard_keys
+            .iter()
+            .find(|(_, key)| *key == KeyboardKey::Enter);
+        assert_eq!(enter.unwrap().0.width, 5);
+    }
+
+    #[test]
+    fn test_the_stats_have_a_bar_for_each_number_of_guesses() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["crane", "plumb", "slump"] {
+            app.input = word.to_string();
+            app.on_enter_press();
+        }
+        app.stats = Stats {
+            games_played: 4,
+            wins: 3,
+            current_streak: 2,
+            max_streak: 3,
+            distribution: vec![0, 1, 2],
+            ..Default::default()
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 7)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                draw_stats(frame, &app, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let lines: Vec<String> = buffer_lines(buffer)
+            .iter()
+            .map(|line| line.trim_end().to_string())
+            .collect();
+        // 36 columns are left for the longest bar, and every bar gets at least one
+        assert_eq!(
+            lines,
+            [
+                "Played 4  Win % 75  Streak 2  Max 3".to_string(),
+                "1 █ 0".to_string(),
+                format!("2 {} 1", "█".repeat(18)),
+                format!("3 {} 2", "█".repeat(36)),
+                "4 █ 0".to_string(),
+                "5 █ 0".to_string(),
+                "6 █ 0".to_string(),
+            ]
+        );
+        // the game that just ended took three guesses
+        assert_eq!(buffer.get(2, 3).fg, app.theme.keyboard_in_right_place_color);
+        assert_eq!(buffer.get(2, 2).fg, app.theme.keyboard_not_guessed_color);
+    }
+
+    #[test]
+    fn test_there_is_a_bar_for_each_guess_the_board_has() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                max_guesses: 4,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["crane", "slump"] {
+            app.input = word.to_string();
+            app.on_enter_press();
+        }
+        app.stats = Stats {
+            games_played: 1,
+            wins: 1,
+            current_streak: 1,
+            max_streak: 1,
+            distribution: vec![0, 1],
+            ..Default::default()
+        };
+
+        let lines: Vec<String> = stats_lines(&app, 40)
+            .iter()
+            .map(|line| {
+                line.0
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect();
+        let labels: Vec<&str> = lines[1..].iter().map(|line| &line[..1]).collect();
+        assert_eq!(labels, ["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn test_keys_that_are_not_letters_are_colored_too() {
+        let path =
+            std::env::temp_dir().join(format!("wordlet-apostrophes-{}.txt", std::process::id()));
+        std::fs::write(&path, "don't\ncan't\nwon't\nx-ray\n").unwrap();
+        let options = GameOptions {
+            dictionary_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("don't".to_string()),
+                words: Some(options.validate().unwrap()),
+                ..options
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        let _ = std::fs::remove_file(&path);
+        for c in "can't".chars() {
+            app.on_letter_entered(c);
+        }
+        app.on_enter_press();
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 8)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                draw_keyboard(frame, &mut app, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let color = |letter: char| {
+            let (rect, _) = app
+                .keyboard_keys
+                .iter()
+                .find(|(_, key)| *key == KeyboardKey::Letter(letter))
+                .unwrap();
+            buffer.get(rect.x, rect.y).fg
+        };
+        assert_eq!(color('\''), app.theme.keyboard_in_right_place_color);
+        assert_eq!(color('n'), app.theme.keyboard_in_right_place_color);
+        assert_eq!(color('c'), app.theme.keyboard_not_in_word_color);
+        assert_eq!(color('-'), app.theme.keyboard_not_guessed_color);
+    }
+
+    #[test]
+    fn test_the_bottom_row_has_enter_and_backspace_keys() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        let draw = |app: &mut App, width: u16| {
+            let mut terminal = Terminal::new(TestBackend::new(width, 5)).unwrap();
+            terminal
+                .draw(|frame| {
+                    let area = frame.size();
+                    draw_keyboard(frame, app, area);
+                })
+                .unwrap();
+            buffer_lines(terminal.backend().buffer())
+        };
+
+        let lines = draw(&mut app, 27);
+        assert_eq!(lines[3], "│  ENTER z x c v b n m ⌫  │");
+
+        // over a four letter board ENTER is too wide, so it's a symbol
+        let lines = draw(&mut app, 22);
+        assert_eq!(lines[3], "│  ⏎ z x c v b n m ⌫ │");
+    }
+
+    #[test]
+    fn test_keys_that_do_not_fit_are_not_clickable() {
+        let keys = keyboard_key_rects(&crate::app::english_keyboard_rows(), Rect::new(0, 0, 12, 4));
+        // two rows fit between the borders, and five keys of each
+        assert_eq!(
+            keys.iter().map(|(_, key)| *key).collect::<Vec<_>>(),
+            "qwertasdfg"
+                .chars()
+                .map(KeyboardKey::Letter)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(keys[5].0, Rect::new(1, 2, 1, 1));
+    }
+
+    #[test]
+    fn test_guessed_letters_are_marked_with_symbols() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            theme: Theme {
+                use_symbols: true,
+                ..Theme::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = String::from("slept");
+        app.on_enter_press();
+        app.revealed_cells = None;
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        // s and l are in the right place, e and t aren't in the word, and p is in the wrong spot
+        let lines = draw_lines(&mut app, width, height);
+        let top_border = lines.iter().find(|line| line.contains('+')).unwrap();
+        let markers: String = top_border.chars().filter(|c| "+~x".contains(*c)).collect();
+        assert_eq!(markers, "++x~x");
+    }
+
+    #[test]
+    fn test_a_new_row_is_colored_one_cell_at_a_time() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            theme: Theme {
+                use_symbols: true,
+                ..Theme::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = String::from("slump");
+        app.on_enter_press();
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let markers = |lines: &[String]| lines.iter().map(|l| l.matches('+').count()).max();
+
+        // nothing is colored yet, and the end screen waits for the row
+        let lines = draw_lines(&mut app, width, height);
+        assert_eq!(markers(&lines), Some(0));
+        assert!(!lines.iter().any(|line| line.contains("Time: ")));
+
+        app.on_tick();
+        app.on_tick();
+        assert_eq!(markers(&draw_lines(&mut app, width, height)), Some(2));
+
+        for _ in 0..3 {
+            app.on_tick();
+        }
+        let lines = draw_lines(&mut app, width, height);
+        assert!(lines.iter().any(|line| line.contains("Time: ")));
+    }
+
+    #[test]
+    fn test_there_are_no_symbols_by_default() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.input = String::from("slept");
+        app.on_enter_press();
+        app.revealed_cells = None;
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, width, height);
+        assert!(!lines
+            .iter()
+            .any(|line| line.contains('~') || line.contains("┏x")));
+    }
+
+    #[test]
+    fn test_the_help_is_drawn_over_a_dimmed_board() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.show_help = true;
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| {
+                let _r = draw(frame, &mut app);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let lines = buffer_lines(buffer);
+        assert!(lines.iter().any(|line| line.contains("How to play")));
+        assert!(buffer.get(0, 0).modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn test_the_letter_states_are_listed_with_symbols() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = String::from("slept");
+        app.on_enter_press();
+
+        let text = letter_states_text(&app);
+        assert!(text.contains("a ·   b ·   c ·   d ·\n"));
+        assert!(text.contains("e x"));
+        assert!(text.contains("l +"));
+        assert!(text.contains("p ~"));
+
+        app.show_letter_states = true;
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, width, height);
+        assert!(lines.iter().any(|line| line.contains("y ·   z ·")));
+        assert!(lines.iter().any(|line| line.contains("Press ! or Esc")));
+    }
+
+    #[test]
+    fn test_the_end_screen_shows_the_score_and_the_answer() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = String::from("slump");
+        app.on_enter_press();
+        app.revealed_cells = None;
+        let text = end_screen_text(&app, &app.end_screen().unwrap());
+
+        assert!(text.starts_with("You win! 1/6"));
+        assert!(text.contains("The answer was 'slump'.\nIt means a sudden fall"));
+        assert!(text.contains("Time: 0:00"));
+        assert!(text.ends_with("Press r to play again, or any other key to exit."));
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, width, height);
+        assert!(lines.iter().any(|line| line.contains("You win! 1/6")));
+        assert!(lines.iter().any(|line| line.contains("It means")));
+    }
+
+    #[test]
+    fn test_the_legend_is_drawn_under_the_keyboard_when_asked_for() {
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let legend_lines = |show_legend: bool, width: u16| {
+            let mut app = App::new(AppOptions {
+                show_legend,
+                ..Default::default()
+            })
+            .unwrap();
+            draw_lines(&mut app, width, height)
+        };
+
+        for width in [width, 60] {
+            let lines = legend_lines(true, width);
+            let text = lines.join("\n");
+            for label in ["right spot", "wrong spot", "not in word"] {
+                assert!(text.contains(label), "{label} is missing at width {width}");
+            }
+            let keyboard = lines
+                .iter()
+                .position(|line| line.contains("Available Letters"));
+            let legend = lines.iter().position(|line| line.contains("right spot"));
+            assert!(legend > keyboard);
+            // all of the keyboard still fits
+            assert!(lines.iter().any(|line| line.contains("ENTER")));
+        }
+
+        let lines = legend_lines(false, width);
+        assert!(!lines.iter().any(|line| line.contains("right spot")));
+    }
+
+    #[test]
+    fn test_a_reviewed_guess_is_read_out_in_the_header() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["crane", "plumb"] {
+            app.input = word.to_string();
+            app.on_enter_press();
+        }
+        app.revealed_cells = None;
+        app.on_key(KeyEvent::from(KeyCode::Up));
+        app.on_key(KeyEvent::from(KeyCode::Up));
+
+        let text = draw_lines(&mut app, 60, 50).join("\n");
+        // the header is as wide as the board, so the letters wrap
+        assert!(text.contains("Guess 1: c=absent"));
+        assert!(text.contains("n=absent e=absent"));
+        // the first row has the double border
+        assert!(text.contains("╔═══╗"));
+    }
+
+    #[test]
+    fn test_the_status_bar_shows_the_guess_and_the_clock() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("slump".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        for word in ["crane", "plumb"] {
+            app.input = word.to_string();
+            app.on_enter_press();
+        }
+        app.solve_time = Some(Duration::from_secs(84));
+        assert_eq!(status_text(&app), "Guess 3/6 · 1:24");
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, width, height);
+        assert!(lines[lines.len() - 1].contains("Guess 3/6 · 1:24"));
+        // and only there, not in the title as well
+        assert_eq!(lines.iter().filter(|line| line.contains("1:24")).count(), 1);
+        // the keyboard above it is all still there
+        assert!(lines.iter().any(|line| line.contains("ENTER")));
+
+        // a won game stays on the guess that won it
+        app.input = "slump".to_string();
+        app.on_enter_press();
+        assert!(status_text(&app).starts_with("Guess 3/6 · "));
+    }
+
+    #[test]
+    fn test_the_header_asks_before_quitting() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.on_key(KeyEvent::from(KeyCode::Esc));
+
+        let (width, height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, width, height);
+        assert!(lines.iter().any(|line| line.contains("Quit? (y/n)")));
+        assert!(!lines.iter().any(|line| line.contains("Welcome")));
+    }
+
+    #[test]
+    fn test_the_end_screen_leaves_out_a_missing_definition() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("about".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = String::from("about");
+        app.on_enter_press();
+        let text = end_screen_text(&app, &app.end_screen().unwrap());
+
+        assert!(text.contains("The answer was 'about'.\nTime: 0:00"));
+        assert!(!text.contains("It means"));
+    }
+
+    fn draw_lines(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| {
+                let _r = draw(frame, app);
+            })
+            .unwrap();
+        buffer_lines(terminal.backend().buffer())
+    }
+
+    fn draw_active_row(app: &mut App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(25, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                let cells = (0..5).map(|i| Rect::new(i * 5, 0, 5, 3)).collect();
+                render_active_row(frame, app, cells);
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    #[test]
+    fn test_the_cell_for_the_next_letter_is_highlighted() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.input = String::from("sl");
+        let buffer = draw_active_row(&mut app);
+
+        // the top left corner of every cell
+        let borders: Vec<_> = (0..5).map(|i| buffer.get(i * 5, 0).fg).collect();
+        assert_eq!(borders[2], app.theme.cursor_color);
+        assert_ne!(borders[2], borders[1]);
+        assert_ne!(borders[2], borders[3]);
+        assert_eq!(borders[0], app.theme.border_color);
+    }
+
+    #[test]
+    fn test_a_rejected_guess_turns_the_whole_row_red() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.input = String::from("sl");
+        app.on_enter_press();
+        let buffer = draw_active_row(&mut app);
+
+        for i in 0..5 {
+            assert_eq!(buffer.get(i * 5, 0).fg, app.theme.header_text_error_color);
+        }
+    }
+
+    #[test]
+    fn test_there_is_no_cursor_once_the_row_is_full() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        app.input = String::from("slump");
+        let buffer = draw_active_row(&mut app);
+
+        assert!((0..5).all(|i| buffer.get(i * 5, 0).fg == app.theme.border_color));
+    }
+
+    #[test]
+    fn test_terminal_size_check() {
+        let (min_width, min_height) = minimum_terminal_size(1, 5, 6);
+        assert!(fits_in_terminal(1, 5, 6, 80, 40));
+        assert!(fits_in_terminal(1, 5, 6, min_width, min_height));
+        assert!(!fits_in_terminal(1, 5, 6, min_width - 1, 40));
+        assert!(!fits_in_terminal(1, 5, 6, 80, min_height - 1));
+        assert!(!fits_in_terminal(1, 8, 6, min_width, min_height));
+        assert!(!fits_in_terminal(1, 5, 10, min_width, min_height));
+    }
+
+    #[test]
+    fn test_the_board_draws_at_the_minimum_terminal_size() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        let (min_width, min_height) = minimum_terminal_size(1, 5, 6);
+        let lines = draw_lines(&mut app, min_width, min_height);
+        assert!(lines.iter().any(|line| line.contains("Wordlet")));
+        assert!(lines.iter().any(|line| line.contains("z x c v b n m")));
+    }
+
+    #[test]
+    fn test_a_tiny_terminal_shows_a_message_instead_of_the_board() {
+        let mut app = App::new(AppOptions::default()).unwrap();
+        let lines = draw_lines(&mut app, 20, 5);
+        assert!(lines[0].contains("Terminal too small"));
+        assert!(!lines.iter().any(|line| line.contains("Wordlet")));
+    }
+
+    #[test]
+    fn test_several_boards_are_drawn_side_by_side() {
+        let mut app = App::new(AppOptions {
+            boards: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = "z".to_string();
+
+        let (min_width, min_height) = minimum_terminal_size(2, 5, 7);
+        assert!(min_width > minimum_terminal_size(1, 5, 7).0);
+        let lines = draw_lines(&mut app, min_width, min_height);
+        // the letter being typed goes on both boards
+        assert!(lines.iter().any(|line| line.matches("│ z │").count() == 2));
+        assert!(!lines[0].contains("Terminal too small"));
+    }
+
+    #[test]
+    fn test_the_board_has_a_column_for_every_letter() {
+        let mut app = App::new(AppOptions {
+            game_config: GameOptions {
+                answer: Some("planet".to_string()),
+                word_length: 6,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+        app.input = "plan".to_string();
+
+        let (min_width, min_height) = minimum_terminal_size(1, 6, 6);
+        // the active row has six cells, with the typed letters in the first four
+        let lines = draw_lines(&mut app, min_width, min_height);
+        let active_row = lines.iter().find(|line| line.contains('p')).unwrap();
+        assert_eq!(active_row.matches('│').count(), 6 * 2 + 2);
+    }
+}